@@ -0,0 +1,103 @@
+/// Generates the dispatch table of the template binary.
+///
+/// Every solution in `src/bin/<year>-<day>.rs` is included as a module of the template binary,
+/// which lets `all` and `solve` run solutions in-process instead of spawning `cargo run`.
+///
+/// It also passes the year of `aoc.toml` to the compiler as `AOC_YEAR`, unless that is set
+/// already, which is the year of solutions that only pass a day to `solution!`.
+use std::{env, fs, path::Path};
+
+fn is_solution_file(name: &str) -> bool {
+    let Some(stem) = name.strip_suffix(".rs") else {
+        return false;
+    };
+    let Some((year, day)) = stem.split_once('-') else {
+        return false;
+    };
+    year.len() == 4
+        && day.len() == 2
+        && year.chars().all(|c| c.is_ascii_digit())
+        && day.chars().all(|c| c.is_ascii_digit())
+}
+
+/// The top-level `year` of a config file. Like the template, it accepts an integer or a string,
+/// e.g. `year = 2023` or `year = "2023"`.
+fn configured_year(config: &str) -> Option<&str> {
+    let (_, value) = config
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default().trim())
+        .take_while(|line| !line.starts_with('['))
+        .filter_map(|line| line.split_once('='))
+        .find(|(key, _)| key.trim() == "year")?;
+
+    let value = value.trim();
+    let year = value
+        .strip_prefix('"')
+        .and_then(|quoted| quoted.strip_suffix('"'))
+        .unwrap_or(value);
+
+    // anything else is reported by the template when it reads the config.
+    (year.len() == 4 && year.chars().all(|c| c.is_ascii_digit())).then_some(year)
+}
+
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+
+    let config_path = Path::new(&manifest_dir).join("aoc.toml");
+    println!("cargo:rerun-if-changed={}", config_path.display());
+    println!("cargo:rerun-if-env-changed=AOC_YEAR");
+
+    if env::var("AOC_YEAR").is_err() {
+        let config = fs::read_to_string(&config_path).unwrap_or_default();
+        if let Some(year) = configured_year(&config) {
+            println!("cargo:rustc-env=AOC_YEAR={year}");
+        }
+    }
+
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+    println!("cargo:rerun-if-changed={}", bin_dir.display());
+
+    let mut names: Vec<String> = fs::read_dir(&bin_dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter_map(|entry| entry.file_name().into_string().ok())
+                .filter(|name| is_solution_file(name))
+                .collect()
+        })
+        .unwrap_or_default();
+    names.sort();
+
+    let mut modules = String::new();
+    let mut entries = String::new();
+
+    for name in &names {
+        let module = format!(
+            "solution_{}",
+            name.trim_end_matches(".rs").replace('-', "_")
+        );
+        let path = bin_dir.join(name);
+
+        // diagnostics for solutions are reported when compiling their own binaries.
+        modules.push_str(&format!(
+            "#[cfg(not(test))]\n#[allow(warnings, clippy::all, clippy::pedantic)]\n#[path = {:?}]\nmod {module};\n",
+            path.display().to_string()
+        ));
+        entries.push_str(&format!("    {module}::SOLUTION,\n"));
+    }
+
+    let table = format!(
+        "{modules}
+/// Every solution in `src/bin`, ordered by year and day.
+#[cfg(not(test))]
+pub const SOLUTIONS: &[advent_of_code::template::registry::Entry] = &[
+{entries}];
+
+#[cfg(test)]
+pub const SOLUTIONS: &[advent_of_code::template::registry::Entry] = &[];
+"
+    );
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("solutions.rs"), table).unwrap();
+}
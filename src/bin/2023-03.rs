@@ -1,6 +1,6 @@
 use std::collections::{HashMap, HashSet};
 
-advent_of_code::solution!(3);
+advent_of_code::solution!(2023, 3);
 
 const NEIGHBORS: [(i32, i32); 8] = [
     (-1, 0),
@@ -113,13 +113,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(4361));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(467835));
     }
 }
@@ -1,4 +1,4 @@
-advent_of_code::solution!(5);
+advent_of_code::solution!(2023, 5, parse = parse_input);
 
 use itertools::Itertools;
 use regex::Regex;
@@ -90,27 +90,18 @@ pub fn propagate_seed(seed: u64, maps: &Maps) -> u64 {
     value
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let (seeds, maps) = parse_input(input);
-
-    seeds
-        .iter()
-        .map(|&s| propagate_seed(s, &maps))
-        .min()
-        .and_then(|v| Some(v as u32))
+pub fn part_one((seeds, maps): &(Seeds, Maps)) -> Option<u64> {
+    seeds.iter().map(|&s| propagate_seed(s, maps)).min()
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let (seeds, maps) = parse_input(input);
-
+pub fn part_two((seeds, maps): &(Seeds, Maps)) -> Option<u64> {
     // brute forcing because I'm done with this problem. be sure to
     // run with --release flag. took X minutes on my machine.
     seeds
         .chunks_exact(2)
         .flat_map(|pair| (pair[0])..(pair[0] + pair[1]))
-        .map(|s| propagate_seed(s, &maps))
+        .map(|s| propagate_seed(s, maps))
         .min()
-        .and_then(|v| Some(v as u32))
 }
 
 #[cfg(test)]
@@ -119,13 +110,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&parse_input(&advent_of_code::template::read_file(
+            "examples", PUZZLE,
+        )));
         assert_eq!(result, Some(35));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&parse_input(&advent_of_code::template::read_file(
+            "examples", PUZZLE,
+        )));
         assert_eq!(result, Some(46));
     }
 }
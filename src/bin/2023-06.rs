@@ -1,6 +1,7 @@
+use advent_of_code::template::solution::Error;
 use itertools::Itertools;
 
-advent_of_code::solution!(6);
+advent_of_code::solution!(2023, 6);
 
 pub fn roots(t: u64, d: u64) -> (u64, u64) {
     let ft = t as f64;
@@ -19,17 +20,17 @@ pub fn vectorize(s: &str, skip: usize) -> Vec<u64> {
         .collect::<Vec<u64>>()
 }
 
-pub fn solve(time: &Vec<u64>, distance: &Vec<u64>) -> Option<u32> {
-    let answer = time
-        .iter()
+pub fn solve(time: &Vec<u64>, distance: &Vec<u64>) -> Result<u64, Error> {
+    time.iter()
         .zip(distance.iter())
         .map(|(t, d)| roots(*t, *d))
         .map(|(r1, r2)| r2 - r1 + 1)
-        .product::<u64>();
-    Some(answer as u32)
+        .try_fold(1_u64, |acc, ways| {
+            acc.checked_mul(ways).ok_or(Error::Overflow)
+        })
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
+pub fn part_one(input: &str) -> Result<u64, Error> {
     let (time, distance): (Vec<u64>, Vec<u64>) = input
         .split_once('\n')
         .map(|pair| (vectorize(pair.0, 1), vectorize(pair.1, 1)))
@@ -38,7 +39,7 @@ pub fn part_one(input: &str) -> Option<u32> {
     solve(&time, &distance)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(input: &str) -> Result<u64, Error> {
     let get_num = |l: &str| {
         l.split_once(':')
             .unwrap()
@@ -55,7 +56,7 @@ pub fn part_two(input: &str) -> Option<u32> {
         .map(|(t, d)| roots(t, d))
         .map(|(r1, r2)| r2 - r1 + 1)
         .unwrap();
-    Some(answer as u32)
+    Ok(answer)
 }
 
 #[cfg(test)]
@@ -64,13 +65,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(288));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Ok(288));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Ok(71503));
     }
 }
@@ -6,7 +6,7 @@ use std::{
 
 use itertools::Itertools;
 
-advent_of_code::solution!(7);
+advent_of_code::solution!(2023, 7);
 
 ////////////////////////////////////////////////////////////////////////////////
 /// Card and Hand Type
@@ -237,13 +237,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(6440));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(5905));
     }
 }
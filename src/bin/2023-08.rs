@@ -1,7 +1,7 @@
 use std::collections::{HashMap, HashSet};
 use std::iter::repeat;
 
-advent_of_code::solution!(8);
+advent_of_code::solution!(2023, 8);
 
 type Graph<'a> = HashMap<&'a str, (&'a str, &'a str)>;
 
@@ -76,13 +76,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file_part(
+            "examples", PUZZLE, 1,
+        ));
         assert_eq!(result, Some(6));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file_part(
+            "examples", PUZZLE, 2,
+        ));
         assert_eq!(result, Some(6));
     }
 }
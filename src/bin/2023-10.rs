@@ -1,6 +1,6 @@
 use std::collections::{HashSet, VecDeque};
 
-advent_of_code::solution!(10);
+advent_of_code::solution!(2023, 10);
 
 type Grid = Vec<Vec<char>>;
 type Point = (i32, i32);
@@ -81,13 +81,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, None);
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, None);
     }
 }
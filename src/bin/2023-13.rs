@@ -1,6 +1,6 @@
 use itertools::Itertools;
 
-advent_of_code::solution!(13);
+advent_of_code::solution!(2023, 13);
 
 type Grid = Vec<Vec<char>>;
 
@@ -93,13 +93,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(405));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(400));
     }
 }
@@ -1,6 +1,6 @@
 use std::collections::{VecDeque, HashSet};
 
-advent_of_code::solution!(16);
+advent_of_code::solution!(2023, 16);
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Direction {
@@ -125,13 +125,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(46));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(51));
     }
 }
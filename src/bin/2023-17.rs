@@ -1,6 +1,6 @@
 use std::collections::{HashMap, VecDeque};
 
-advent_of_code::solution!(17);
+advent_of_code::solution!(2023, 17);
 
 type Grid = Vec<Vec<usize>>;
 type Point = (i32, i32);
@@ -135,13 +135,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(102));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, None);
     }
 }
@@ -1,4 +1,8 @@
 mod day;
+mod puzzle;
 pub mod template;
+mod year;
 
 pub use day::*;
+pub use puzzle::*;
+pub use year::*;
@@ -1,92 +1,88 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve};
-use args::{parse, AppArguments};
+use std::io;
 
-mod args {
-    use std::process;
+use advent_of_code::template::cli::{Cli, Command};
+use advent_of_code::template::commands::{
+    all, bench_compare, download, examples, read, scaffold, solve, stars, status, verify,
+};
+use advent_of_code::template::runner::RunOptions;
+use clap::{CommandFactory, Parser};
 
-    use advent_of_code::Day;
-
-    pub enum AppArguments {
-        Download {
-            day: Day,
-        },
-        Read {
-            day: Day,
-        },
-        Scaffold {
-            day: Day,
-        },
-        Solve {
-            day: Day,
-            release: bool,
-            time: bool,
-            submit: Option<u8>,
-        },
-        All {
-            release: bool,
-            time: bool,
-        },
-    }
-
-    pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
-        let mut args = pico_args::Arguments::from_env();
-
-        let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
-                release: args.contains("--release"),
-                time: args.contains("--time"),
-            },
-            Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
-            },
-            Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
-            },
-            Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
-            },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                time: args.contains("--time"),
-            },
-            Some(x) => {
-                eprintln!("Unknown command: {x}");
-                process::exit(1);
-            }
-            None => {
-                eprintln!("No command specified.");
-                process::exit(1);
-            }
-        };
-
-        let remaining = args.finish();
-        if !remaining.is_empty() {
-            eprintln!("Warning: unknown argument(s): {remaining:?}.");
-        }
-
-        Ok(app_args)
-    }
+/// The dispatch table of all solutions, generated by `build.rs`.
+mod solutions {
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
 }
 
 fn main() {
-    match parse() {
-        Err(err) => {
-            eprintln!("Error: {err}");
-            std::process::exit(1);
-        }
-        Ok(args) => match args {
-            AppArguments::All { release, time } => all::handle(release, time),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day } => scaffold::handle(day),
-            AppArguments::Solve {
-                day,
-                release,
+    match Cli::parse().command {
+        Command::All {
+            year,
+            release,
+            time,
+            format,
+            timeout,
+            jobs,
+            part,
+            only_failing,
+            skip_slow,
+        } => {
+            let options = RunOptions {
                 time,
-                submit,
-            } => solve::handle(day, release, time, submit),
-        },
-    };
+                submit: None,
+                format,
+                quiet: false,
+                timeout,
+                part,
+            };
+            let filter = all::Filter {
+                only_failing,
+                skip_slow,
+            };
+            all::handle(
+                solutions::SOLUTIONS,
+                year.year(),
+                release,
+                options,
+                jobs.get(),
+                filter,
+            );
+        }
+        Command::Verify { year, day, timeout } => {
+            verify::handle(solutions::SOLUTIONS, year.year(), day, timeout);
+        }
+        Command::BenchCompare { year, threshold } => {
+            bench_compare::handle(solutions::SOLUTIONS, year.year(), threshold);
+        }
+        Command::Status {
+            year,
+            format,
+            timeout,
+            tests,
+        } => status::handle(solutions::SOLUTIONS, year.year(), format, timeout, tests),
+        Command::Stars {
+            year,
+            file,
+            id,
+            user,
+        } => stars::handle(year.year(), file.as_deref(), id, user),
+        Command::Download { puzzle, force } => download::handle(puzzle.puzzle(), force),
+        Command::Read { puzzle } => read::handle(puzzle.puzzle()),
+        Command::Examples { puzzle, blocks } => examples::handle(puzzle.puzzle(), &blocks),
+        Command::Scaffold {
+            puzzle,
+            template,
+            answer,
+            download,
+        } => scaffold::handle(puzzle.puzzle(), &template, &answer, download),
+        Command::Solve { puzzle, run, .. } => {
+            solve::handle(solutions::SOLUTIONS, puzzle.puzzle(), &run.into());
+        }
+        Command::Completions { shell } => {
+            clap_complete::generate(
+                shell,
+                &mut Cli::command(),
+                "advent_of_code",
+                &mut io::stdout(),
+            );
+        }
+    }
 }
@@ -0,0 +1,112 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::template::config;
+use crate::{Day, Year};
+
+/// Identifies a single puzzle by its [`Year`] and [`Day`].
+///
+/// # Display
+/// This value displays as `<year>-<day>`, which is also the name of the puzzle's solution binary.
+///
+/// ```
+/// # use advent_of_code::{day, year, PuzzleId};
+/// let puzzle = PuzzleId::new(year!(2023), day!(8));
+/// assert_eq!(puzzle.to_string(), "2023-08")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PuzzleId {
+    pub year: Year,
+    pub day: Day,
+}
+
+impl PuzzleId {
+    /// Creates a [`PuzzleId`] from a year and a day.
+    pub const fn new(year: Year, day: Day) -> Self {
+        Self { year, day }
+    }
+
+    /// The name of the binary that holds the solution for this puzzle.
+    pub fn bin_name(&self) -> String {
+        self.to_string()
+    }
+
+    /// The path of the module file that holds the solution for this puzzle.
+    pub fn bin_path(&self) -> String {
+        format!("src/bin/{}.rs", self.bin_name())
+    }
+
+    /// The path of a data file for this puzzle, e.g. `data/2023/inputs/01.txt`. The data
+    /// directory is [configurable](crate::template::config).
+    pub fn data_path(&self, folder: &str, extension: &str) -> String {
+        config::get().data_path(&format!("{}/{folder}/{}.{extension}", self.year, self.day))
+    }
+
+    /// The path of a numbered data file for this puzzle, e.g. `data/2023/examples/08-2.txt`.
+    pub fn numbered_data_path(&self, folder: &str, number: u8, extension: &str) -> String {
+        config::get().data_path(&format!(
+            "{}/{folder}/{}-{number}.{extension}",
+            self.year, self.day
+        ))
+    }
+}
+
+impl Display for PuzzleId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.year, self.day)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for PuzzleId {
+    type Err = PuzzleIdFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (year, day) = s.split_once(['-', '/']).ok_or(PuzzleIdFromStrError)?;
+        Ok(Self {
+            year: year.parse().map_err(|_| PuzzleIdFromStrError)?,
+            day: day.parse().map_err(|_| PuzzleIdFromStrError)?,
+        })
+    }
+}
+
+/// An error which can be returned when parsing a [`PuzzleId`].
+#[derive(Debug)]
+pub struct PuzzleIdFromStrError;
+
+impl Error for PuzzleIdFromStrError {}
+
+impl Display for PuzzleIdFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a puzzle in the format `<year>-<day>`")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::PuzzleId;
+    use crate::{day, year};
+
+    #[test]
+    fn formats_paths() {
+        let puzzle = PuzzleId::new(year!(2023), day!(5));
+        assert_eq!(puzzle.bin_name(), "2023-05");
+        assert_eq!(puzzle.bin_path(), "src/bin/2023-05.rs");
+        assert_eq!(puzzle.data_path("inputs", "txt"), "data/2023/inputs/05.txt");
+    }
+
+    #[test]
+    fn parses_from_str() {
+        let expected = PuzzleId::new(year!(2022), day!(12));
+        assert_eq!("2022-12".parse::<PuzzleId>().unwrap(), expected);
+        assert_eq!("2022/12".parse::<PuzzleId>().unwrap(), expected);
+        assert!("2022".parse::<PuzzleId>().is_err());
+        assert!("2022-26".parse::<PuzzleId>().is_err());
+    }
+}
+
+/* -------------------------------------------------------------------------- */
@@ -0,0 +1,232 @@
+/// Known-correct answers, stored per puzzle in `data/<year>/answers/<day>.toml`.
+///
+/// The files use a small subset of TOML: `part_one` and `part_two` keys with either an integer
+/// or a basic string value.
+///
+/// ```toml
+/// part_one = 142
+/// part_two = "ABCDEF"
+/// ```
+use std::{fmt::Display, fs, io};
+
+use crate::template::write_file;
+use crate::PuzzleId;
+
+/// The name of answers files in error messages.
+const FILE: &str = "answers file";
+
+/// An error reading or parsing a file of the template, e.g. an answers, examples or config file.
+/// Each variant names the file, e.g. `answers file`.
+#[derive(Debug)]
+pub enum Error {
+    Parser(&'static str, String),
+    IO(&'static str, io::Error),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(file, message) => write!(f, "could not parse {file}: {message}"),
+            Error::IO(file, e) => write!(f, "could not read {file}: {e}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part_one: Option<String>,
+    pub part_two: Option<String>,
+}
+
+impl Answers {
+    /// Returns the stored answer for a part, if any.
+    #[must_use]
+    pub fn get(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_one.as_deref(),
+            2 => self.part_two.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Stores the answer for a part. Parts other than `1` and `2` are ignored.
+    pub fn set(&mut self, part: u8, answer: &str) {
+        match part {
+            1 => self.part_one = Some(answer.to_string()),
+            2 => self.part_two = Some(answer.to_string()),
+            _ => {}
+        }
+    }
+
+    #[must_use]
+    pub fn to_toml(&self) -> String {
+        [("part_one", &self.part_one), ("part_two", &self.part_two)]
+            .iter()
+            .filter_map(|(key, value)| {
+                value
+                    .as_deref()
+                    .map(|value| format!("{key} = {}\n", format_value(value)))
+            })
+            .collect()
+    }
+}
+
+#[must_use]
+pub fn get_path(puzzle: PuzzleId) -> String {
+    puzzle.data_path("answers", "toml")
+}
+
+/// Loads the stored answers for a puzzle. A missing file yields empty [`Answers`].
+pub fn load(puzzle: PuzzleId) -> Result<Answers, Error> {
+    match fs::read_to_string(get_path(puzzle)) {
+        Ok(content) => parse(&content),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+        Err(e) => Err(Error::IO(FILE, e)),
+    }
+}
+
+/// Writes the answers for a puzzle, creating the answers directory if necessary.
+pub fn save(puzzle: PuzzleId, answers: &Answers) -> Result<(), Error> {
+    write_file(get_path(puzzle), answers.to_toml()).map_err(|e| Error::IO(FILE, e))
+}
+
+fn parse(content: &str) -> Result<Answers, Error> {
+    let mut answers = Answers::default();
+
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let (key, value) = line.split_once('=').ok_or_else(|| {
+            Error::Parser(FILE, format!("line {}: expected `key = value`", index + 1))
+        })?;
+
+        let value = parse_value(value.trim())
+            .ok_or_else(|| Error::Parser(FILE, format!("line {}: invalid value", index + 1)))?;
+
+        match key.trim() {
+            "part_one" => answers.part_one = Some(value),
+            "part_two" => answers.part_two = Some(value),
+            key => {
+                return Err(Error::Parser(
+                    FILE,
+                    format!("line {}: unknown key `{key}`", index + 1),
+                ))
+            }
+        }
+    }
+
+    Ok(answers)
+}
+
+/// Parses an integer or basic string value.
+pub fn parse_value(value: &str) -> Option<String> {
+    if let Some(quoted) = value.strip_prefix('"') {
+        return parse_basic_string(quoted);
+    }
+
+    let value = value.split('#').next()?.trim();
+    is_integer(value).then(|| value.to_string())
+}
+
+/// Formats a value as an integer if possible, or as a basic string.
+pub fn format_value(value: &str) -> String {
+    if is_integer(value) {
+        return value.to_string();
+    }
+
+    let mut encoded = String::from('"');
+    for c in value.chars() {
+        match c {
+            '\n' => encoded.push_str("\\n"),
+            '\t' => encoded.push_str("\\t"),
+            '"' => encoded.push_str("\\\""),
+            '\\' => encoded.push_str("\\\\"),
+            c => encoded.push(c),
+        }
+    }
+    encoded.push('"');
+    encoded
+}
+
+fn is_integer(value: &str) -> bool {
+    let digits = value.strip_prefix('-').unwrap_or(value);
+    !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Parses the remainder of a basic string after its opening quote.
+fn parse_basic_string(s: &str) -> Option<String> {
+    let mut value = String::new();
+    let mut chars = s.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                let rest = chars.as_str().trim();
+                return (rest.is_empty() || rest.starts_with('#')).then_some(value);
+            }
+            '\\' => match chars.next()? {
+                'n' => value.push('\n'),
+                't' => value.push('\t'),
+                '"' => value.push('"'),
+                '\\' => value.push('\\'),
+                _ => return None,
+            },
+            c => value.push(c),
+        }
+    }
+
+    None
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse, Answers};
+
+    #[test]
+    fn round_trips_answers() {
+        let mut answers = Answers::default();
+        answers.set(1, "-42");
+        answers.set(2, "#.\"\n.#");
+        assert_eq!(
+            answers.to_toml(),
+            "part_one = -42\npart_two = \"#.\\\"\\n.#\"\n"
+        );
+        assert_eq!(parse(&answers.to_toml()).unwrap(), answers);
+    }
+
+    #[test]
+    fn parses_answers() {
+        let answers = parse("# day 1\npart_one = 142\npart_two = \"ABC\" # letters\n").unwrap();
+        assert_eq!(
+            answers,
+            Answers {
+                part_one: Some("142".into()),
+                part_two: Some("ABC".into()),
+            }
+        );
+        assert_eq!(answers.get(1), Some("142"));
+        assert_eq!(answers.get(3), None);
+    }
+
+    #[test]
+    fn parses_escaped_strings() {
+        let answers = parse(r##"part_two = "#.#\n.#.""##).unwrap();
+        assert_eq!(answers.part_one, None);
+        assert_eq!(answers.part_two.unwrap(), "#.#\n.#.");
+    }
+
+    #[test]
+    fn rejects_malformed_files() {
+        assert!(parse("part_one 142").is_err());
+        assert_eq!(
+            parse("part_three = 1").unwrap_err().to_string(),
+            "could not parse answers file: line 1: unknown key `part_three`"
+        );
+        assert!(parse("part_one = abc").is_err());
+        assert!(parse("part_one = \"unterminated").is_err());
+    }
+}
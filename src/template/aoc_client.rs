@@ -0,0 +1,352 @@
+/// A built-in client for the Advent of Code website, enabled with the `client` feature.
+///
+/// It replaces the external aoc-cli: requests are authenticated with the session cookie of a
+/// logged-in browser, read from `session` in `aoc.toml` or `~/.adventofcode.session`. Inputs and
+/// puzzle pages are cached in `data/.cache`. The base URL can be changed with `base_url`, e.g. to
+/// test against a local server.
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::template::config;
+use crate::template::leaderboard::MIN_REFRESH_INTERVAL;
+use crate::template::markdown;
+use crate::template::submissions::Verdict;
+use crate::template::throttle;
+use crate::template::write_file;
+use crate::{PuzzleId, Year};
+
+/// The cache folder, relative to the data directory.
+pub const CACHE_DIR: &str = ".cache";
+
+/// Advent of Code asks automated tools to identify themselves.
+const USER_AGENT: &str = "github.com/fspoettel/advent-of-code-rust";
+
+#[derive(Debug)]
+pub enum ClientError {
+    /// No session cookie was found.
+    NoSession,
+    /// The server responded with an error status.
+    Status(u16),
+    /// The server could not be reached.
+    Transport(String),
+    IO(io::Error),
+}
+
+impl From<io::Error> for ClientError {
+    fn from(e: io::Error) -> Self {
+        ClientError::IO(e)
+    }
+}
+
+impl From<ureq::Error> for ClientError {
+    fn from(e: ureq::Error) -> Self {
+        match e {
+            ureq::Error::Status(status, _) => ClientError::Status(status),
+            ureq::Error::Transport(transport) => ClientError::Transport(transport.to_string()),
+        }
+    }
+}
+
+impl Display for ClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClientError::NoSession => write!(
+                f,
+                "no session cookie found. Set `session` in aoc.toml or AOC_SESSION, or store it in ~/.adventofcode.session."
+            ),
+            ClientError::Status(404) => write!(f, "the puzzle is not available (yet)."),
+            ClientError::Status(400 | 500) => {
+                write!(
+                    f,
+                    "the server rejected the request, is the session cookie valid?"
+                )
+            }
+            ClientError::Status(status) => write!(f, "the server responded with status {status}."),
+            ClientError::Transport(message) => write!(f, "could not reach the server: {message}"),
+            ClientError::IO(e) => write!(f, "could not access the cache: {e}"),
+        }
+    }
+}
+
+/// The response of the server to a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubmitResponse {
+    pub verdict: Verdict,
+    /// The text of the response, e.g. `That's the right answer!`.
+    pub message: String,
+}
+
+pub struct Client {
+    base_url: String,
+    session: String,
+    cache_dir: PathBuf,
+    min_interval: Duration,
+}
+
+impl Client {
+    #[must_use]
+    pub fn new(base_url: &str, session: &str, cache_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+            cache_dir: cache_dir.into(),
+            min_interval: throttle::MIN_INTERVAL,
+        }
+    }
+
+    /// Creates a client from the configured session cookie and base URL.
+    pub fn from_env() -> Result<Self, ClientError> {
+        let config = config::get();
+        let session = read_session().ok_or(ClientError::NoSession)?;
+        let cache_dir = config.data_path(CACHE_DIR);
+        Ok(Self::new(&config.base_url, &session, &cache_dir))
+    }
+
+    /// Downloads the personal puzzle input. Inputs never change, so they are served from the
+    /// cache once downloaded.
+    pub fn input(&self, puzzle: PuzzleId) -> Result<String, ClientError> {
+        let cache_path = self.cache_path(puzzle, "input.txt");
+
+        if let Ok(input) = fs::read_to_string(&cache_path) {
+            return Ok(input);
+        }
+
+        let input = self.get(&format!("{}/input", self.puzzle_url(puzzle)))?;
+        write_file(&cache_path, &input)?;
+        Ok(input)
+    }
+
+    /// Downloads the puzzle description and converts it to markdown. The page is cached until an
+    /// answer is accepted, which unlocks part two.
+    pub fn puzzle(&self, puzzle: PuzzleId) -> Result<String, ClientError> {
+        let cache_path = self.cache_path(puzzle, "puzzle.html");
+
+        let html = match fs::read_to_string(&cache_path) {
+            Ok(html) => html,
+            Err(_) => {
+                let html = self.get(&self.puzzle_url(puzzle))?;
+                write_file(&cache_path, &html)?;
+                html
+            }
+        };
+
+        Ok(markdown::from_html(&html))
+    }
+
+    /// Submits an answer and parses the verdict from the response.
+    pub fn submit(
+        &self,
+        puzzle: PuzzleId,
+        part: u8,
+        answer: &str,
+    ) -> Result<SubmitResponse, ClientError> {
+        throttle::wait(self.min_interval);
+
+        let html = ureq::post(&format!("{}/answer", self.puzzle_url(puzzle)))
+            .set("Cookie", &format!("session={}", self.session))
+            .set("User-Agent", USER_AGENT)
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])?
+            .into_string()?;
+
+        let message = markdown::from_html(&html).trim().to_string();
+        let verdict = Verdict::from_response(&message);
+
+        if verdict == Verdict::Correct {
+            // the description of part two is only shown after part one was solved.
+            self.remove_cached(puzzle, "puzzle.html")?;
+        }
+
+        Ok(SubmitResponse { verdict, message })
+    }
+
+    /// Downloads the JSON of a private leaderboard. It is served from the cache for
+    /// [`MIN_REFRESH_INTERVAL`], as Advent of Code asks to not request it more often.
+    pub fn leaderboard(&self, year: Year, id: &str) -> Result<String, ClientError> {
+        let cache_path = self
+            .cache_dir
+            .join(year.to_string())
+            .join(format!("leaderboard-{id}.json"));
+
+        let is_fresh = fs::metadata(&cache_path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .is_some_and(|age| age < MIN_REFRESH_INTERVAL);
+
+        if is_fresh {
+            if let Ok(json) = fs::read_to_string(&cache_path) {
+                return Ok(json);
+            }
+        }
+
+        let json = self.get(&format!(
+            "{}/{year}/leaderboard/private/view/{id}.json",
+            self.base_url
+        ))?;
+        write_file(&cache_path, &json)?;
+        Ok(json)
+    }
+
+    /// Removes the cached input and puzzle page, so they are downloaded again.
+    pub fn clear_cache(&self, puzzle: PuzzleId) -> Result<(), ClientError> {
+        self.remove_cached(puzzle, "input.txt")?;
+        self.remove_cached(puzzle, "puzzle.html")
+    }
+
+    fn remove_cached(&self, puzzle: PuzzleId, file: &str) -> Result<(), ClientError> {
+        match fs::remove_file(self.cache_path(puzzle, file)) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    fn get(&self, url: &str) -> Result<String, ClientError> {
+        throttle::wait(self.min_interval);
+
+        Ok(ureq::get(url)
+            .set("Cookie", &format!("session={}", self.session))
+            .set("User-Agent", USER_AGENT)
+            .call()?
+            .into_string()?)
+    }
+
+    fn puzzle_url(&self, puzzle: PuzzleId) -> String {
+        format!(
+            "{}/{}/day/{}",
+            self.base_url,
+            puzzle.year,
+            puzzle.day.into_inner()
+        )
+    }
+
+    fn cache_path(&self, puzzle: PuzzleId, file: &str) -> PathBuf {
+        self.cache_dir
+            .join(puzzle.year.to_string())
+            .join(format!("{}-{file}", puzzle.day))
+    }
+}
+
+/// Reads the configured session cookie, or the one in the files aoc-cli uses.
+fn read_session() -> Option<String> {
+    if let Some(session) = &config::get().session {
+        return Some(session.clone());
+    }
+
+    let home = env::var("HOME").or_else(|_| env::var("USERPROFILE")).ok()?;
+
+    [".adventofcode.session", ".config/adventofcode.session"]
+        .iter()
+        .find_map(|file| fs::read_to_string(Path::new(&home).join(file)).ok())
+        .map(|session| session.trim().to_string())
+        .filter(|session| !session.is_empty())
+}
+
+#[cfg(all(feature = "test_lib", feature = "client"))]
+mod tests {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use std::time::Duration;
+    use std::{env, fs, thread};
+
+    use super::Client;
+    use crate::template::submissions::Verdict;
+    use crate::{day, year, PuzzleId};
+
+    /// Serves `responses` in order and sends each request line and body back.
+    fn mock_server(responses: Vec<(u16, &'static str)>) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (sender, receiver) = mpsc::channel();
+
+        thread::spawn(move || {
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut request = String::new();
+                let mut content_length = 0;
+
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(length) = line.to_lowercase().strip_prefix("content-length:") {
+                        content_length = length.trim().parse().unwrap();
+                    }
+                    if line.starts_with("Cookie") || request.is_empty() {
+                        request.push_str(line.trim_end());
+                        request.push(' ');
+                    }
+                    if line == "\r\n" {
+                        break;
+                    }
+                }
+
+                let mut content = vec![0; content_length];
+                reader.read_exact(&mut content).unwrap();
+                request.push_str(&String::from_utf8_lossy(&content));
+                sender.send(request).unwrap();
+
+                write!(
+                    reader.get_mut(),
+                    "HTTP/1.1 {status} OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+        });
+
+        (url, receiver)
+    }
+
+    /// A client with its own cache that does not wait between requests.
+    fn client(url: &str, name: &str) -> Client {
+        let dir = env::temp_dir().join(format!("aoc-client-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+
+        Client {
+            min_interval: Duration::ZERO,
+            ..Client::new(url, "secret", dir)
+        }
+    }
+
+    #[test]
+    fn downloads_and_caches_inputs() {
+        let (url, requests) = mock_server(vec![(200, "1\n2\n")]);
+        let client = client(&url, "input");
+        let puzzle = PuzzleId::new(year!(2023), day!(6));
+
+        assert_eq!(client.input(puzzle).unwrap(), "1\n2\n");
+        assert_eq!(
+            requests.recv().unwrap(),
+            "GET /2023/day/6/input HTTP/1.1 Cookie: session=secret "
+        );
+
+        // the second call is served from the cache, the server would not answer it.
+        assert_eq!(client.input(puzzle).unwrap(), "1\n2\n");
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (url, requests) = mock_server(vec![
+            (200, "<main><article><p>That's the right answer! You are <em>one gold star</em> closer.</p></article></main>"),
+            (404, ""),
+        ]);
+        let client = client(&url, "submit");
+        let puzzle = PuzzleId::new(year!(2023), day!(6));
+
+        let response = client.submit(puzzle, 1, "288").unwrap();
+        assert_eq!(response.verdict, Verdict::Correct);
+        assert!(response.message.starts_with("That's the right answer!"));
+        assert_eq!(
+            requests.recv().unwrap(),
+            "POST /2023/day/6/answer HTTP/1.1 Cookie: session=secret level=1&answer=288"
+        );
+
+        assert!(client.puzzle(puzzle).is_err());
+    }
+}
@@ -0,0 +1,238 @@
+/// Persists benchmark results across runs in `data/benchmarks/history.jsonl`.
+///
+/// Every benched part is appended as one JSON object per line, keyed by the git commit and the
+/// time of the run. The most recent entry of a part serves as the baseline for comparisons.
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    process::Command,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::template::config;
+use crate::template::create_parent_dir;
+use crate::template::json::{self, Value};
+use crate::template::report::PartReport;
+use crate::{Day, PuzzleId, Year};
+
+/// The file benchmarks are recorded in, relative to the data directory.
+pub const HISTORY_PATH: &str = "benchmarks/history.jsonl";
+
+/// Slowdowns of the median beyond this percentage are flagged as regressions by default.
+pub const DEFAULT_THRESHOLD_PERCENT: f64 = 10.0;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub commit: String,
+    pub timestamp: u64,
+    pub puzzle: PuzzleId,
+    pub part: u8,
+    pub median: Duration,
+    pub p95: Duration,
+    pub stddev: Duration,
+    pub samples: u128,
+}
+
+impl Entry {
+    /// Creates an entry from a benched part. Returns [`None`] if the part was not benched.
+    #[must_use]
+    pub fn from_report(report: &PartReport, commit: &str, timestamp: u64) -> Option<Self> {
+        let stats = report.stats?;
+        Some(Self {
+            commit: commit.to_string(),
+            timestamp,
+            puzzle: report.puzzle,
+            part: report.part,
+            median: stats.median,
+            p95: stats.p95,
+            stddev: stats.stddev,
+            samples: report.samples,
+        })
+    }
+
+    fn to_json(&self) -> String {
+        format!(
+            r#"{{"commit":{},"timestamp":{},"year":{},"day":{},"part":{},"median_ns":{},"p95_ns":{},"stddev_ns":{},"samples":{}}}"#,
+            json::encode_string(&self.commit),
+            self.timestamp,
+            self.puzzle.year.into_inner(),
+            self.puzzle.day.into_inner(),
+            self.part,
+            self.median.as_nanos(),
+            self.p95.as_nanos(),
+            self.stddev.as_nanos(),
+            self.samples
+        )
+    }
+
+    fn from_json(line: &str) -> Option<Self> {
+        let object = json::parse_object(line)?;
+        let number = |key: &str| object.get(key).and_then(Value::as_u64);
+
+        let year = Year::new(u16::try_from(number("year")?).ok()?)?;
+        let day = Day::new(u8::try_from(number("day")?).ok()?)?;
+
+        Some(Self {
+            commit: object.get("commit")?.as_str()?.to_string(),
+            timestamp: number("timestamp")?,
+            puzzle: PuzzleId::new(year, day),
+            part: u8::try_from(number("part")?).ok()?,
+            median: Duration::from_nanos(number("median_ns")?),
+            p95: Duration::from_nanos(number("p95_ns")?),
+            stddev: Duration::from_nanos(number("stddev_ns")?),
+            samples: u128::from(number("samples")?),
+        })
+    }
+}
+
+/// How the current timing of a part compares to its baseline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub baseline: Duration,
+    pub current: Duration,
+    /// The relative change of the median in percent. Positive values mean slower.
+    pub change_percent: f64,
+    pub is_regression: bool,
+}
+
+#[must_use]
+pub fn compare(baseline: Duration, current: Duration, threshold_percent: f64) -> Comparison {
+    #[allow(clippy::cast_precision_loss)]
+    let change_percent = if baseline.is_zero() {
+        0.0
+    } else {
+        (current.as_nanos() as f64 / baseline.as_nanos() as f64 - 1.0) * 100.0
+    };
+
+    Comparison {
+        baseline,
+        current,
+        change_percent,
+        is_regression: change_percent > threshold_percent,
+    }
+}
+
+/// Returns the most recent entry for a part.
+#[must_use]
+pub fn baseline(entries: &[Entry], puzzle: PuzzleId, part: u8) -> Option<&Entry> {
+    entries
+        .iter()
+        .filter(|entry| entry.puzzle == puzzle && entry.part == part)
+        .max_by_key(|entry| entry.timestamp)
+}
+
+/// Reads the benchmark history. A missing file yields an empty history.
+pub fn load() -> io::Result<Vec<Entry>> {
+    match fs::read_to_string(config::get().data_path(HISTORY_PATH)) {
+        Ok(content) => Ok(content.lines().filter_map(Entry::from_json).collect()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(vec![]),
+        Err(e) => Err(e),
+    }
+}
+
+/// Appends the benched parts of a run to the history.
+pub fn record(reports: &[PartReport]) -> io::Result<()> {
+    let commit = current_commit();
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+
+    let entries: Vec<Entry> = reports
+        .iter()
+        .filter_map(|report| Entry::from_report(report, &commit, timestamp))
+        .collect();
+
+    if entries.is_empty() {
+        return Ok(());
+    }
+
+    let path = config::get().data_path(HISTORY_PATH);
+    create_parent_dir(&path)?;
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+
+    for entry in entries {
+        writeln!(file, "{}", entry.to_json())?;
+    }
+
+    Ok(())
+}
+
+/// The short hash of the checked-out commit, suffixed with `-dirty` if there are uncommitted changes.
+fn current_commit() -> String {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+
+    match git(&["rev-parse", "--short", "HEAD"]) {
+        Some(commit) if git(&["status", "--porcelain"]).is_some_and(|s| !s.is_empty()) => {
+            format!("{commit}-dirty")
+        }
+        Some(commit) => commit,
+        None => "unknown".into(),
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{baseline, compare, Entry};
+    use crate::{day, year, PuzzleId};
+
+    fn entry(timestamp: u64, part: u8, median_ns: u64) -> Entry {
+        Entry {
+            commit: "abc1234".into(),
+            timestamp,
+            puzzle: PuzzleId::new(year!(2023), day!(3)),
+            part,
+            median: Duration::from_nanos(median_ns),
+            p95: Duration::from_nanos(median_ns * 2),
+            stddev: Duration::from_nanos(10),
+            samples: 100,
+        }
+    }
+
+    #[test]
+    fn round_trips_entries() {
+        let e = entry(1_701_388_800, 2, 1500);
+        assert_eq!(
+            e.to_json(),
+            r#"{"commit":"abc1234","timestamp":1701388800,"year":2023,"day":3,"part":2,"median_ns":1500,"p95_ns":3000,"stddev_ns":10,"samples":100}"#
+        );
+        assert_eq!(Entry::from_json(&e.to_json()), Some(e));
+        assert_eq!(Entry::from_json("{}"), None);
+    }
+
+    #[test]
+    fn picks_latest_baseline() {
+        let entries = [
+            entry(1, 1, 100),
+            entry(3, 1, 300),
+            entry(2, 1, 200),
+            entry(4, 2, 400),
+        ];
+        let puzzle = PuzzleId::new(year!(2023), day!(3));
+        assert_eq!(baseline(&entries, puzzle, 1).unwrap().timestamp, 3);
+        assert_eq!(baseline(&entries, puzzle, 2).unwrap().timestamp, 4);
+        assert!(baseline(&entries, PuzzleId::new(year!(2023), day!(4)), 1).is_none());
+    }
+
+    #[test]
+    fn flags_regressions_beyond_threshold() {
+        let slower = compare(Duration::from_nanos(100), Duration::from_nanos(125), 10.0);
+        assert!((slower.change_percent - 25.0).abs() < 1e-9);
+        assert!(slower.is_regression);
+
+        let noise = compare(Duration::from_nanos(100), Duration::from_nanos(105), 10.0);
+        assert!(!noise.is_regression);
+
+        let faster = compare(Duration::from_nanos(100), Duration::from_nanos(50), 10.0);
+        assert!(!faster.is_regression);
+    }
+}
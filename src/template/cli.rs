@@ -0,0 +1,327 @@
+/// The command-line interface of the template binary and of standalone solution binaries.
+///
+/// Commands are declared with `clap`, which generates `--help` for every command and the shell
+/// completions printed by `completions`. Solution binaries accept the same flags as `solve` and
+/// turn them into a single [`RunConfig`].
+use std::{num::NonZeroUsize, path::PathBuf, process, time::Duration};
+
+use clap::{Args, Parser, Subcommand};
+use clap_complete::Shell;
+
+use crate::template::bench_history::DEFAULT_THRESHOLD_PERCENT;
+use crate::template::config::{self, CONFIG_PATH};
+use crate::template::input::Input;
+use crate::template::report::OutputFormat;
+use crate::template::runner::{parse_timeout, RunOptions};
+use crate::template::scaffold_templates::{ANSWER_TYPES, DEFAULT_ANSWER_TYPE, DEFAULT_TEMPLATE};
+use crate::{Day, PuzzleId, Year};
+
+/// Solve Advent of Code puzzles.
+#[derive(Debug, Parser)]
+#[command(name = "advent_of_code", version)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Create the solution and data files of a day.
+    Scaffold {
+        #[command(flatten)]
+        puzzle: PuzzleArgs,
+        /// The template of the solution, from `templates/` or built in.
+        #[arg(long, default_value = DEFAULT_TEMPLATE)]
+        template: String,
+        /// The type parts return.
+        #[arg(long, default_value = DEFAULT_ANSWER_TYPE, value_parser = ANSWER_TYPES.to_vec())]
+        answer: String,
+        /// Download the input and description, and extract the example.
+        #[arg(long)]
+        download: bool,
+    },
+    /// Download the input and description of a day.
+    Download {
+        #[command(flatten)]
+        puzzle: PuzzleArgs,
+        /// Download files that already exist again.
+        #[arg(long)]
+        force: bool,
+    },
+    /// Show the description of a day.
+    Read {
+        #[command(flatten)]
+        puzzle: PuzzleArgs,
+    },
+    /// List the code blocks of a description, or write them to example files.
+    Examples {
+        #[command(flatten)]
+        puzzle: PuzzleArgs,
+        /// Write the code block with this number to an example file. Can be repeated.
+        #[arg(long = "block", value_name = "N")]
+        blocks: Vec<usize>,
+    },
+    /// Run the solution of a day.
+    Solve {
+        #[command(flatten)]
+        puzzle: PuzzleArgs,
+        #[command(flatten)]
+        run: RunArgs,
+        /// Accepted for compatibility, the aliases always build in release mode.
+        #[arg(long, hide = true)]
+        release: bool,
+    },
+    /// Run the solutions of every day.
+    All {
+        #[command(flatten)]
+        year: YearArgs,
+        /// Together with `--time`, update the benchmarks in the readme.
+        #[arg(long)]
+        release: bool,
+        /// Benchmark each part.
+        #[arg(long)]
+        time: bool,
+        /// How results are written to stdout.
+        #[arg(long, value_enum, default_value_t = config::get().format)]
+        format: OutputFormat,
+        /// Give up on a part after this many seconds.
+        #[arg(long, value_name = "SECONDS", value_parser = parse_timeout)]
+        timeout: Option<Duration>,
+        /// Run up to this many days at the same time.
+        #[arg(long, default_value = "1")]
+        jobs: NonZeroUsize,
+        /// Only run this part of each day.
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        /// Only run days whose parts did not all solve in their last run.
+        #[arg(long)]
+        only_failing: bool,
+        /// Skip days that took longer than this many milliseconds in their last run.
+        #[arg(long, value_name = "MS", value_parser = parse_millis)]
+        skip_slow: Option<Duration>,
+    },
+    /// Compare the solutions against the stored answers.
+    Verify {
+        #[command(flatten)]
+        year: YearArgs,
+        /// Only verify this day.
+        day: Option<Day>,
+        /// Give up on a part after this many seconds. Defaults to 10.
+        #[arg(long, value_name = "SECONDS", value_parser = parse_timeout)]
+        timeout: Option<Duration>,
+    },
+    /// Compare the benchmarks against previous runs.
+    BenchCompare {
+        #[command(flatten)]
+        year: YearArgs,
+        /// Report parts that got slower by more than this many percent.
+        #[arg(long, default_value_t = DEFAULT_THRESHOLD_PERCENT)]
+        threshold: f64,
+    },
+    /// Show the progress of every day.
+    Status {
+        #[command(flatten)]
+        year: YearArgs,
+        /// How results are written to stdout.
+        #[arg(long, value_enum, default_value_t = config::get().format)]
+        format: OutputFormat,
+        /// Give up on a part after this many seconds. Defaults to 10. A part that timed out keeps
+        /// running in the background, which can slow down the days after it.
+        #[arg(long, value_name = "SECONDS", value_parser = parse_timeout)]
+        timeout: Option<Duration>,
+        /// Run the tests of every solution.
+        #[arg(long)]
+        tests: bool,
+    },
+    /// Show the stars of a private leaderboard and write yours to the readme.
+    #[command(alias = "leaderboard")]
+    Stars {
+        #[command(flatten)]
+        year: YearArgs,
+        /// Read the leaderboard from a file instead of downloading it.
+        #[arg(long)]
+        file: Option<PathBuf>,
+        /// The id of the leaderboard. Defaults to `leaderboard_id` in `aoc.toml`, then to your user id.
+        #[arg(long)]
+        id: Option<String>,
+        /// Your user id. Defaults to `user_id` in `aoc.toml`.
+        #[arg(long)]
+        user: Option<String>,
+    },
+    /// Print the shell completions of this binary.
+    Completions { shell: Shell },
+}
+
+#[derive(Debug, Clone, Copy, Args)]
+pub struct YearArgs {
+    /// The year of the puzzles. Defaults to `year` in `aoc.toml` or `AOC_YEAR`.
+    #[arg(long)]
+    pub year: Option<Year>,
+}
+
+impl YearArgs {
+    /// The year that was passed, or the configured one. Exits if neither is set.
+    #[must_use]
+    pub fn year(&self) -> Year {
+        match self.year.or(config::get().year) {
+            Some(year) => year,
+            None => {
+                eprintln!("Error: no year specified. Pass `--year <year>`, or set `year` in {CONFIG_PATH} or AOC_YEAR.");
+                process::exit(1);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Args)]
+pub struct PuzzleArgs {
+    /// The day of the puzzle, from 1 to 25.
+    pub day: Day,
+    #[command(flatten)]
+    pub year: YearArgs,
+}
+
+impl PuzzleArgs {
+    #[must_use]
+    pub fn puzzle(&self) -> PuzzleId {
+        PuzzleId::new(self.year.year(), self.day)
+    }
+}
+
+/// The flags of `solve`, which standalone solution binaries accept as well.
+#[derive(Debug, Clone, Default, Args)]
+pub struct RunArgs {
+    /// Benchmark each part.
+    #[arg(long)]
+    pub time: bool,
+    /// Submit the answer of this part.
+    #[arg(
+        long,
+        value_name = "PART",
+        value_parser = clap::value_parser!(u8).range(1..=2),
+        conflicts_with_all = ["input", "example"],
+    )]
+    pub submit: Option<u8>,
+    /// How results are written to stdout.
+    #[arg(long, value_enum, default_value_t = config::get().format)]
+    pub format: OutputFormat,
+    /// Give up on a part after this many seconds.
+    #[arg(long, value_name = "SECONDS", value_parser = parse_timeout)]
+    pub timeout: Option<Duration>,
+    /// Read the input from this file, or from stdin if it is `-`.
+    #[arg(long, value_name = "PATH", conflicts_with = "example")]
+    pub input: Option<String>,
+    /// Run against the example, or the numbered example `N`. Pass it after the day.
+    #[arg(long, value_name = "N", num_args = 0..=1)]
+    pub example: Option<Option<u8>>,
+}
+
+/// The arguments of a standalone solution binary, e.g. `cargo run --bin 2023-01 -- --time`.
+#[derive(Debug, Parser)]
+#[command(about = "Run this solution.")]
+struct SolutionCli {
+    #[command(flatten)]
+    run: RunArgs,
+}
+
+/// What a solution runs against and how.
+#[derive(Debug, Clone, Default)]
+pub struct RunConfig {
+    pub input: Input,
+    pub options: RunOptions,
+}
+
+impl RunConfig {
+    /// Parses the arguments of a standalone solution binary, exiting with a usage message if they
+    /// are invalid.
+    #[must_use]
+    pub fn from_args() -> Self {
+        SolutionCli::parse().run.into()
+    }
+}
+
+impl From<RunArgs> for RunConfig {
+    fn from(args: RunArgs) -> Self {
+        let input = match (args.input, args.example) {
+            (Some(path), _) => Input::from_path(&path),
+            (None, Some(example)) => Input::Example(example),
+            (None, None) => Input::Puzzle,
+        };
+
+        Self {
+            input,
+            options: RunOptions {
+                time: args.time,
+                submit: args.submit,
+                format: args.format,
+                quiet: false,
+                timeout: args.timeout,
+                part: None,
+            },
+        }
+    }
+}
+
+fn parse_millis(millis: &str) -> Result<Duration, String> {
+    millis
+        .parse()
+        .map(Duration::from_millis)
+        .map_err(|_| format!("invalid duration `{millis}`, expected a number of milliseconds"))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use clap::{CommandFactory, Parser};
+
+    use super::{Cli, Command, RunConfig};
+    use crate::template::input::Input;
+    use crate::{day, year};
+
+    fn parse(args: &[&str]) -> Result<Command, clap::Error> {
+        let args = ["advent_of_code"].iter().chain(args);
+        Cli::try_parse_from(args).map(|cli| cli.command)
+    }
+
+    #[test]
+    fn has_a_valid_model() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn parses_solve() {
+        let Ok(Command::Solve { puzzle, run, .. }) =
+            parse(&["solve", "5", "--year", "2022", "--example", "2", "--time"])
+        else {
+            panic!("expected solve");
+        };
+
+        assert_eq!(puzzle.day, day!(5));
+        assert_eq!(puzzle.year.year, Some(year!(2022)));
+
+        let config = RunConfig::from(run);
+        assert_eq!(config.input, Input::Example(Some(2)));
+        assert!(config.options.time);
+
+        let Ok(Command::Solve { run, .. }) = parse(&["solve", "5", "--example"]) else {
+            panic!("expected solve");
+        };
+        assert_eq!(RunConfig::from(run).input, Input::Example(None));
+
+        assert!(parse(&["solve", "5", "--release"]).is_ok());
+    }
+
+    #[test]
+    fn validates_parts() {
+        assert!(parse(&["solve", "1", "--year", "2023", "--submit", "2"]).is_ok());
+        assert!(parse(&["solve", "1", "--submit", "3"]).is_err());
+        assert!(parse(&["solve", "1", "--submit", "1", "--example"]).is_err());
+        assert!(parse(&["all", "--year", "2023", "--part", "0"]).is_err());
+    }
+
+    #[test]
+    fn rejects_unknown_commands() {
+        assert!(parse(&["solv", "1"]).is_err());
+        assert!(parse(&["solve", "26"]).is_err());
+        assert!(parse(&["solve", "1", "--tim"]).is_err());
+    }
+}
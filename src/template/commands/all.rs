@@ -1,40 +1,180 @@
+use std::collections::BTreeMap;
 use std::io;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
 
 use crate::template::{
+    bench_history,
+    last_run::{self, Outcome},
     readme_benchmarks::{self, Timings},
-    ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+    registry::{self, Entry},
+    report::{self, OutputFormat, PartReport, PartStatus, RunReport},
+    runner::{self, RunOptions},
+    supervisor, try_read_file, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
 };
-use crate::{all_days, Day};
+use crate::{all_days, Day, PuzzleId, Year};
+
+/// Selects the days `cargo all` runs, based on the outcome of their last run.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Filter {
+    /// Only run days that did not solve all selected parts in their last run.
+    pub only_failing: bool,
+    /// Skip days whose selected parts took longer than this in total in their last run.
+    pub skip_slow: Option<Duration>,
+}
+
+impl Filter {
+    /// Whether days may be skipped by the filter.
+    #[must_use]
+    pub fn is_active(&self) -> bool {
+        self.only_failing || self.skip_slow.is_some()
+    }
+}
+
+pub fn handle(
+    solutions: &[Entry],
+    year: Year,
+    is_release: bool,
+    options: RunOptions,
+    jobs: usize,
+    filter: Filter,
+) {
+    let RunOptions {
+        time: is_timed,
+        format,
+        ..
+    } = options;
+    let is_text = format == OutputFormat::Text;
+
+    // concurrent runs compete for the CPU and would skew the benchmarks.
+    let jobs = if is_timed && jobs > 1 {
+        eprintln!("Running days sequentially, as benchmarks are skewed by concurrent runs.");
+        1
+    } else {
+        jobs
+    };
+
+    let outcomes = last_run::load().unwrap_or_else(|e| {
+        eprintln!("Could not read the outcomes of the last run: {e}");
+        vec![]
+    });
+    let days: Vec<Day> = all_days()
+        .filter(|day| {
+            let puzzle = PuzzleId::new(year, *day);
+            is_selected(
+                &filter,
+                &outcomes,
+                &options,
+                registry::find(solutions, puzzle),
+                puzzle,
+            )
+        })
+        .collect();
 
-pub fn handle(is_release: bool, is_timed: bool) {
     let mut timings: Vec<Timings> = vec![];
+    let mut reports: Vec<RunReport> = vec![];
+
+    let mut finish_day = |day: Day, run: DayRun| match run {
+        // skip days that have not been scaffolded yet.
+        DayRun::NotScaffolded if is_text => println!("Not solved."),
+        DayRun::NotScaffolded => {}
+        DayRun::NoInput(e) => {
+            eprintln!(
+                "Could not read input file for {}: {e}",
+                PuzzleId::new(year, day)
+            );
+        }
+        DayRun::Ran(report) => {
+            timings.push(collect_timings(day, &report));
+            reports.push(report);
+        }
+    };
 
-    all_days().for_each(|day| {
-        if day > 1 {
-            println!();
+    if jobs > 1 {
+        run_in_order(
+            jobs,
+            days.len(),
+            |index| {
+                let puzzle = PuzzleId::new(year, days[index]);
+                let solution = registry::find(solutions, puzzle);
+                let mut events = vec![];
+
+                let run = run_day(solution, puzzle, |solution, input| {
+                    let (report, buffered) = supervisor::run_buffered(solution, input, &options);
+                    events = buffered;
+                    report
+                });
+
+                (run, events)
+            },
+            |index, (run, events)| {
+                let day = days[index];
+
+                if is_text {
+                    let solution = registry::find(solutions, PuzzleId::new(year, day));
+                    print_header(index, day, solution);
+                }
+                for event in &events {
+                    runner::print_event(event, &options);
+                }
+                finish_day(day, run);
+            },
+        );
+    } else {
+        for (index, &day) in days.iter().enumerate() {
+            let puzzle = PuzzleId::new(year, day);
+            let solution = registry::find(solutions, puzzle);
+
+            if is_text {
+                print_header(index, day, solution);
+            }
+
+            let run = run_day(solution, puzzle, |solution, input| {
+                supervisor::run(solution, input, &options)
+            });
+            finish_day(day, run);
         }
+    }
 
-        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-        println!("------");
+    if days.is_empty() {
+        eprintln!("No days match the filter.");
+    }
 
-        let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+    let parts: Vec<PartReport> = reports.iter().flat_map(|r| r.parts.clone()).collect();
+    if let Err(e) = last_run::record(&parts) {
+        eprintln!("Failed to record the outcomes of this run: {e}");
+    }
 
-        if output.is_empty() {
-            println!("Not solved.");
-        } else {
-            let val = child_commands::parse_exec_time(&output, day);
-            timings.push(val);
-        }
-    });
+    report::print_collected(&reports, format);
+
+    if is_text {
+        print_failures(&reports);
+    }
 
     if is_timed {
         let total_millis = timings.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64;
 
-        println!("\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}");
+        if is_text {
+            println!(
+                "\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
+            );
+        }
+
+        // a partial run would drop the skipped days and parts from the table and baselines.
+        if is_release && (filter.is_active() || options.part.is_some()) {
+            eprintln!(
+                "Not updating the README and benchmark history, as only some days or parts ran."
+            );
+        } else if is_release {
+            if let Err(e) = bench_history::record(&parts) {
+                eprintln!("Failed to record benchmark history: {e}");
+            }
 
-        if is_release {
-            match readme_benchmarks::update(timings, total_millis) {
-                Ok(()) => println!("Successfully updated README with benchmarks."),
+            match readme_benchmarks::update(year, timings, total_millis) {
+                Ok(()) if is_text => println!("Successfully updated README with benchmarks."),
+                Ok(()) => eprintln!("Successfully updated README with benchmarks."),
                 Err(_) => {
                     eprintln!("Failed to update readme with benchmarks.");
                 }
@@ -43,212 +183,256 @@ pub fn handle(is_release: bool, is_timed: bool) {
     }
 }
 
-#[derive(Debug)]
-pub enum Error {
-    BrokenPipe,
-    Parser(String),
-    IO(io::Error),
+/// The outcome of running a single day.
+enum DayRun {
+    NotScaffolded,
+    NoInput(io::Error),
+    Ran(RunReport),
 }
 
-impl From<std::io::Error> for Error {
-    fn from(e: std::io::Error) -> Self {
-        Error::IO(e)
+fn run_day(
+    solution: Option<&Entry>,
+    puzzle: PuzzleId,
+    run: impl FnOnce(&Entry, &str) -> RunReport,
+) -> DayRun {
+    let Some(solution) = solution else {
+        return DayRun::NotScaffolded;
+    };
+
+    match try_read_file("inputs", puzzle) {
+        Ok(input) => DayRun::Ran(run(solution, &input)),
+        Err(e) => DayRun::NoInput(e),
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+/// Whether a day passes the filter. Without any filter, unscaffolded days are listed as well.
+fn is_selected(
+    filter: &Filter,
+    outcomes: &[Outcome],
+    options: &RunOptions,
+    solution: Option<&Entry>,
+    puzzle: PuzzleId,
+) -> bool {
+    let parts: Vec<u8> = [1, 2]
+        .into_iter()
+        .filter(|part| options.runs_part(*part))
+        .collect();
+
+    if filter.only_failing
+        && (solution.is_none() || !last_run::is_failing(outcomes, puzzle, &parts))
+    {
+        return false;
+    }
+
+    filter
+        .skip_slow
+        .is_none_or(|threshold| !last_run::is_slow(outcomes, puzzle, &parts, threshold))
 }
 
-/// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
-mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::Day;
-    use std::{
-        io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
-        thread,
-    };
+fn print_header(index: usize, day: Day, solution: Option<&Entry>) {
+    if index > 0 {
+        println!();
+    }
+
+    match solution.and_then(|solution| solution.name) {
+        Some(name) => println!("{ANSI_BOLD}Day {day}: {name}{ANSI_RESET}"),
+        None => println!("{ANSI_BOLD}Day {day}{ANSI_RESET}"),
+    }
+    println!("------");
+}
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+/// Runs `task` for the indices `0..count` on `jobs` threads and passes the results to `finish` in
+/// order of their index, as soon as all previous results are in.
+fn run_in_order<T: Send>(
+    jobs: usize,
+    count: usize,
+    task: impl Fn(usize) -> T + Sync,
+    mut finish: impl FnMut(usize, T),
+) {
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(count) {
+            let sender = sender.clone();
+            let (next, task) = (&next, &task);
+
+            scope.spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                if index >= count || sender.send((index, task(index))).is_err() {
+                    break;
+                }
+            });
         }
+        drop(sender);
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        let mut done = BTreeMap::new();
+        let mut printed = 0;
 
-        if is_release {
-            args.push("--release");
-        }
+        for (index, result) in receiver {
+            done.insert(index, result);
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+            while let Some(result) = done.remove(&printed) {
+                finish(printed, result);
+                printed += 1;
+            }
         }
+    });
+}
 
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+/// Lists the parts that crashed, overflowed or timed out, so they do not get lost in the output.
+fn print_failures(reports: &[RunReport]) {
+    let failures: Vec<&PartReport> = reports
+        .iter()
+        .flat_map(|report| &report.parts)
+        .filter(|part| {
+            matches!(
+                part.status(),
+                PartStatus::Crashed | PartStatus::Overflowed | PartStatus::TimedOut
+            )
+        })
+        .collect();
+
+    if failures.is_empty() {
+        return;
+    }
 
-        let mut cmd = Command::new("cargo")
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
+    println!("\n{ANSI_BOLD}Failures:{ANSI_RESET}");
+
+    for part in failures {
+        let error = part
+            .error
+            .as_ref()
+            .map(ToString::to_string)
+            .unwrap_or_default();
+        println!(
+            "Day {} Part {}: {} ({error})",
+            part.puzzle.day,
+            part.part,
+            part.status()
+        );
+    }
+}
 
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
+/// Collects the timings of the benched parse step and parts for the readme table.
+fn collect_timings(day: Day, report: &RunReport) -> Timings {
+    let mut timings = Timings {
+        day,
+        parse: None,
+        part_1: None,
+        part_2: None,
+        total_nanos: 0_f64,
+    };
 
-        let mut output = vec![];
+    if let Some(parse) = report.parse.as_ref().filter(|parse| parse.stats.is_some()) {
+        timings.parse = parse.stats;
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
-        });
+        #[allow(clippy::cast_precision_loss)]
+        let nanos = parse.duration.as_nanos() as f64;
+        timings.total_nanos += nanos;
+    }
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+    for result in report.parts.iter().filter(|result| result.is_benched()) {
+        match result.part {
+            1 => timings.part_1 = result.stats,
+            2 => timings.part_2 = result.stats,
+            _ => {}
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
-
-        Ok(output)
+        #[allow(clippy::cast_precision_loss)]
+        let nanos = result.duration.as_nanos() as f64;
+        timings.total_nanos += nanos;
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timings {
-        let mut timings = super::Timings {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
-        };
-
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
-
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
-
-                timings.total_nanos += nanos;
-            });
+    timings
+}
 
-        timings
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{collect_timings, run_in_order};
+    use crate::template::report::{BenchStats, ParseReport, PartReport, RunReport};
+    use crate::{day, year, PuzzleId};
+
+    fn part_result(part: u8, nanos: u64, samples: u128) -> PartReport {
+        let duration = Duration::from_nanos(nanos);
+        PartReport {
+            puzzle: PuzzleId::new(year!(2023), day!(1)),
+            part,
+            answer: Some("42".into()),
+            error: None,
+            duration,
+            samples,
+            stats: (samples > 1)
+                .then(|| BenchStats::from_samples(&[duration]))
+                .flatten(),
+        }
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
+    #[test]
+    fn collects_benched_parts() {
+        let res = collect_timings(
+            day!(1),
+            &RunReport {
+                parse: None,
+                parts: vec![part_result(1, 74_130, 100), part_result(2, 2_000_000, 10)],
+            },
+        );
+        assert_eq!(res.total_nanos, 2_074_130_f64);
+        assert!(res.parse.is_none());
+        assert_eq!(res.part_1.unwrap().median, Duration::from_nanos(74_130));
+        assert_eq!(res.part_2.unwrap().median, Duration::from_millis(2));
     }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+    #[test]
+    fn skips_parts_that_were_not_benched() {
+        let res = collect_timings(
+            day!(1),
+            &RunReport {
+                parse: None,
+                parts: vec![part_result(1, 100, 1)],
+            },
+        );
+        assert_eq!(res.total_nanos, 0_f64);
+        assert!(res.part_1.is_none());
+        assert!(res.part_2.is_none());
     }
 
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
+    #[test]
+    fn finishes_in_order() {
+        let mut finished = vec![];
+
+        run_in_order(
+            4,
+            8,
+            |index| {
+                // later indices finish first.
+                std::thread::sleep(Duration::from_millis(8 - index as u64));
+                index * 10
+            },
+            |index, result| finished.push((index, result)),
+        );
+
+        assert_eq!(finished, (0..8).map(|i| (i, i * 10)).collect::<Vec<_>>());
     }
 
-    #[cfg(feature = "test_lib")]
-    mod tests {
-        use super::parse_exec_time;
-
-        use crate::day;
-
-        #[test]
-        fn test_well_formed() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
-        }
-
-        #[test]
-        fn test_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
-        }
-
-        #[test]
-        fn test_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
-        }
+    #[test]
+    fn collects_benched_parse_step() {
+        let duration = Duration::from_nanos(5_000);
+        let res = collect_timings(
+            day!(1),
+            &RunReport {
+                parse: Some(ParseReport {
+                    puzzle: PuzzleId::new(year!(2023), day!(1)),
+                    duration,
+                    samples: 100,
+                    stats: BenchStats::from_samples(&[duration]),
+                }),
+                parts: vec![part_result(1, 1_000, 100)],
+            },
+        );
+        assert_eq!(res.total_nanos, 6_000_f64);
+        assert_eq!(res.parse.unwrap().median, duration);
     }
 }
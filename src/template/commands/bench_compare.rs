@@ -0,0 +1,83 @@
+use std::process;
+
+use crate::template::{
+    bench_history::{self, Comparison},
+    registry::{self, Entry},
+    report::{OutputFormat, PartReport},
+    runner::RunOptions,
+    supervisor, try_read_file, ANSI_BOLD, ANSI_RESET,
+};
+use crate::{all_days, PuzzleId, Year};
+
+pub fn handle(solutions: &[Entry], year: Year, threshold_percent: f64) {
+    let history = match bench_history::load() {
+        Ok(history) => history,
+        Err(e) => {
+            eprintln!("Could not read benchmark history: {e}");
+            process::exit(1);
+        }
+    };
+
+    let options = RunOptions {
+        time: true,
+        quiet: true,
+        format: OutputFormat::Text,
+        ..RunOptions::default()
+    };
+
+    let mut regressions = 0;
+
+    for day in all_days() {
+        let puzzle = PuzzleId::new(year, day);
+
+        let Some(solution) = registry::find(solutions, puzzle) else {
+            continue;
+        };
+
+        let Ok(input) = try_read_file("inputs", puzzle) else {
+            continue;
+        };
+
+        for report in supervisor::run(solution, &input, &options).parts {
+            let comparison =
+                bench_history::baseline(&history, puzzle, report.part).and_then(|baseline| {
+                    report.stats.map(|stats| {
+                        bench_history::compare(baseline.median, stats.median, threshold_percent)
+                    })
+                });
+
+            print_comparison(&report, comparison.as_ref());
+
+            if comparison.is_some_and(|comparison| comparison.is_regression) {
+                regressions += 1;
+            }
+        }
+    }
+
+    if regressions > 0 {
+        eprintln!(
+            "\n{ANSI_BOLD}{regressions} part(s) regressed by more than {threshold_percent}%.{ANSI_RESET}"
+        );
+        process::exit(1);
+    }
+}
+
+fn print_comparison(report: &PartReport, comparison: Option<&Comparison>) {
+    let label = format!("Day {} Part {}", report.puzzle.day, report.part);
+
+    match comparison {
+        None => println!("{label}: {:.1?} (no baseline)", report.duration),
+        Some(comparison) => {
+            let verdict = if comparison.is_regression {
+                format!(" ✖ {ANSI_BOLD}regression{ANSI_RESET}")
+            } else {
+                String::new()
+            };
+
+            println!(
+                "{label}: {:.1?} → {:.1?} ({:+.1}%){verdict}",
+                comparison.baseline, comparison.current, comparison.change_percent
+            );
+        }
+    }
+}
@@ -1,15 +1,102 @@
-use crate::template::aoc_cli;
-use crate::Day;
-use std::process;
+use crate::template::markdown;
+use crate::PuzzleId;
+use std::{fs, process};
+
+/// Which files of a puzzle were downloaded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Downloaded {
+    pub input: bool,
+    pub description: bool,
+}
+
+/// Which files of a puzzle need to be downloaded. Inputs never change, so existing inputs are
+/// kept unless `force` is set; empty ones, as created by `scaffold`, count as missing.
+/// Descriptions are downloaded again until they include part two.
+fn missing(puzzle: PuzzleId, force: bool) -> Downloaded {
+    let input =
+        fs::read_to_string(puzzle.data_path("inputs", "txt")).is_ok_and(|input| !input.is_empty());
+    let description = fs::read_to_string(puzzle.data_path("puzzles", "md"))
+        .is_ok_and(|description| markdown::has_part_two(&description));
+
+    Downloaded {
+        input: force || !input,
+        description: force || !description,
+    }
+}
+
+pub fn handle(puzzle: PuzzleId, force: bool) {
+    match download(puzzle, force) {
+        Ok(Downloaded {
+            input: false,
+            description: false,
+        }) => println!(
+            "🎄 Input and puzzle of {puzzle} are up to date. Type `cargo download {} --year {} --force` to download them again.",
+            puzzle.day, puzzle.year
+        ),
+        Ok(_) => {}
+        Err(e) => {
+            eprintln!("Failed to download {puzzle}: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Downloads the input and description of a puzzle, unless they are up to date.
+#[cfg(not(feature = "client"))]
+pub fn download(puzzle: PuzzleId, force: bool) -> Result<Downloaded, String> {
+    use crate::template::aoc_cli;
+
+    let missing = missing(puzzle, force);
+
+    if !missing.input && !missing.description {
+        return Ok(missing);
+    }
 
-pub fn handle(day: Day) {
     if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+        return Err("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.".into());
     }
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
-    };
+    aoc_cli::download(puzzle, missing.input, missing.description)
+        .map_err(|e| format!("failed to call aoc-cli: {e}"))?;
+
+    Ok(missing)
+}
+
+/// Downloads the input and description of a puzzle, unless they are up to date.
+#[cfg(feature = "client")]
+pub fn download(puzzle: PuzzleId, force: bool) -> Result<Downloaded, String> {
+    use crate::template::{aoc_client::Client, write_file};
+
+    let missing = missing(puzzle, force);
+
+    if !missing.input && !missing.description {
+        return Ok(missing);
+    }
+
+    let input_path = puzzle.data_path("inputs", "txt");
+    let puzzle_path = puzzle.data_path("puzzles", "md");
+
+    let downloaded = Client::from_env().and_then(|client| {
+        if force {
+            client.clear_cache(puzzle)?;
+        }
+
+        let mut files = vec![];
+        if missing.input {
+            files.push(("input", &input_path, client.input(puzzle)?));
+        }
+        if missing.description {
+            files.push(("puzzle", &puzzle_path, client.puzzle(puzzle)?));
+        }
+
+        for (kind, path, content) in &files {
+            write_file(path, content)?;
+            println!("🎄 Successfully wrote {kind} to \"{path}\".");
+        }
+
+        Ok(())
+    });
+
+    downloaded.map_err(|e| e.to_string())?;
+    Ok(missing)
 }
@@ -0,0 +1,129 @@
+use std::{fs, process};
+
+use crate::template::answers::Answers;
+use crate::template::examples::{self, Case};
+use crate::template::markdown::{self, CodeBlock};
+use crate::template::{write_file, ANSI_BOLD, ANSI_RESET};
+use crate::PuzzleId;
+
+/// Lists the code blocks of a puzzle description, or writes the selected ones (numbered from 1)
+/// to example files together with their expected answers.
+pub fn handle(puzzle: PuzzleId, selected: &[usize]) {
+    let puzzle_path = puzzle.data_path("puzzles", "md");
+
+    let markdown = match fs::read_to_string(&puzzle_path) {
+        Ok(markdown) => markdown,
+        Err(e) => {
+            eprintln!(
+                "Could not read puzzle description \"{puzzle_path}\": {e}. Type `cargo download {} --year {}` to download it.",
+                puzzle.day, puzzle.year
+            );
+            process::exit(1);
+        }
+    };
+
+    let blocks = markdown::code_blocks(&markdown);
+
+    if blocks.is_empty() {
+        eprintln!("No code blocks found in \"{puzzle_path}\".");
+        process::exit(1);
+    }
+
+    if selected.is_empty() {
+        list(puzzle, &blocks);
+        return;
+    }
+
+    let chosen: Vec<&CodeBlock> = selected
+        .iter()
+        .map(
+            |&index| match index.checked_sub(1).and_then(|i| blocks.get(i)) {
+                Some(block) => block,
+                None => {
+                    eprintln!(
+                        "There is no code block {index}, expected 1 to {}.",
+                        blocks.len()
+                    );
+                    process::exit(1);
+                }
+            },
+        )
+        .collect();
+
+    write(puzzle, &chosen);
+}
+
+fn list(puzzle: PuzzleId, blocks: &[CodeBlock]) {
+    for (index, block) in blocks.iter().enumerate() {
+        let answers = [1, 2]
+            .into_iter()
+            .filter_map(|part| {
+                block
+                    .answers
+                    .get(part)
+                    .map(|answer| format!("part {part}: {answer}"))
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        println!(
+            "{ANSI_BOLD}Block {}{ANSI_RESET} (part {}, {} line(s){}{})",
+            index + 1,
+            block.part,
+            block.content.lines().count(),
+            if answers.is_empty() { "" } else { "; " },
+            answers
+        );
+        println!("    {}", block.preview(60));
+    }
+
+    println!("---");
+    println!(
+        "🎄 Type `cargo examples {} --year {} --block <n>` to write blocks to example files.",
+        puzzle.day, puzzle.year
+    );
+}
+
+fn write(puzzle: PuzzleId, blocks: &[&CodeBlock]) {
+    let mut cases = vec![];
+
+    for (index, block) in blocks.iter().enumerate() {
+        // a single example keeps the default name, so `--example` picks it up.
+        let path = match blocks.len() {
+            1 => puzzle.data_path("examples", "txt"),
+            _ => puzzle.numbered_data_path(
+                "examples",
+                u8::try_from(index + 1).unwrap_or(u8::MAX),
+                "txt",
+            ),
+        };
+
+        if let Err(e) = write_file(&path, &block.content) {
+            eprintln!("Failed to write example file: {e}");
+            process::exit(1);
+        }
+        println!("Wrote example file \"{path}\"");
+
+        let file = path.rsplit('/').next().unwrap_or_default().to_string();
+        cases.push(Case {
+            name: file.clone(),
+            file: Some(file),
+            input: block.content.clone(),
+            answers: block.answers.clone(),
+        });
+    }
+
+    let examples_path = examples::get_path(puzzle);
+
+    match write_file(&examples_path, examples::to_toml(&cases)) {
+        Ok(()) => println!("Wrote expected answers to \"{examples_path}\""),
+        Err(e) => {
+            eprintln!("Failed to write examples file: {e}");
+            process::exit(1);
+        }
+    }
+
+    if cases.iter().all(|case| case.answers == Answers::default()) {
+        println!("No expected answers found, add them to \"{examples_path}\" by hand.");
+    }
+}
@@ -1,16 +1,41 @@
 use std::process;
 
-use crate::template::aoc_cli;
-use crate::Day;
+use crate::PuzzleId;
+
+#[cfg(not(feature = "client"))]
+pub fn handle(puzzle: PuzzleId) {
+    use crate::template::aoc_cli;
 
-pub fn handle(day: Day) {
     if aoc_cli::check().is_err() {
         eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
     }
 
-    if let Err(e) = aoc_cli::read(day) {
+    if let Err(e) = aoc_cli::read(puzzle) {
         eprintln!("failed to call aoc-cli: {e}");
         process::exit(1);
     };
 }
+
+#[cfg(feature = "client")]
+pub fn handle(puzzle: PuzzleId) {
+    use crate::template::{aoc_client::Client, write_file};
+
+    let puzzle_path = puzzle.data_path("puzzles", "md");
+
+    let description = Client::from_env().and_then(|client| {
+        let description = client.puzzle(puzzle)?;
+
+        write_file(&puzzle_path, &description)?;
+
+        Ok(description)
+    });
+
+    match description {
+        Ok(description) => print!("{description}"),
+        Err(e) => {
+            eprintln!("Failed to read {puzzle}: {e}");
+            process::exit(1);
+        }
+    }
+}
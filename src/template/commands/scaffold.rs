@@ -1,94 +1,253 @@
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::Path,
     process,
 };
 
-use crate::Day;
+use crate::template::answers::Answers;
+use crate::template::commands::download::{self, Downloaded};
+use crate::template::examples::{self, Case};
+use crate::template::markdown::{self, CodeBlock};
+use crate::template::scaffold_templates::{self, Placeholders};
+use crate::template::{create_parent_dir, ANSI_BOLD, ANSI_RESET};
+use crate::PuzzleId;
 
-const MODULE_TEMPLATE: &str = r#"advent_of_code::solution!(DAY_NUMBER);
+const EXAMPLES_TEMPLATE: &str = r#"# Expected answers for the examples, checked by `cargo test`.
+[[case]]
+file = "DAY_FILE"
+# part_one = <answer>
+# part_two = <answer>
+"#;
 
-pub fn part_one(input: &str) -> Option<u32> {
-    None
+/// What scaffolding did to one of the files of a day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Status {
+    Created,
+    /// An untouched examples file was filled in with expected answers.
+    Updated,
+    Exists,
+    /// The file was created empty or exists without content.
+    Empty,
+    Downloaded,
+    /// The example was taken from the code block with this number, counted from 1.
+    Extracted(usize),
+    Missing,
+    Failed(String),
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    None
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Status::Created => f.write_str("created"),
+            Status::Updated => f.write_str("updated with expected answers"),
+            Status::Exists => f.write_str("exists"),
+            Status::Empty => f.write_str("empty"),
+            Status::Downloaded => f.write_str("downloaded"),
+            Status::Extracted(block) => write!(f, "extracted from code block {block}"),
+            Status::Missing => f.write_str("missing"),
+            Status::Failed(e) => write!(f, "failed: {e}"),
+        }
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+impl<E: Display> From<Result<Status, E>> for Status {
+    fn from(result: Result<Status, E>) -> Self {
+        result.unwrap_or_else(|e| Status::Failed(e.to_string()))
+    }
+}
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+/// Writes a file unless it exists.
+fn create_new(path: &str, content: &str) -> io::Result<Status> {
+    create_parent_dir(path)?;
+
+    match OpenOptions::new().write(true).create_new(true).open(path) {
+        Ok(mut file) => {
+            file.write_all(content.as_bytes())?;
+            Ok(if content.is_empty() {
+                Status::Empty
+            } else {
+                Status::Created
+            })
+        }
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+            let is_empty = fs::metadata(path)?.len() == 0;
+            Ok(if is_empty {
+                Status::Empty
+            } else {
+                Status::Exists
+            })
+        }
+        Err(e) => Err(e),
     }
+}
 
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+/// Creates the solution from the template `template`, whose parts return `answer`, and the data
+/// files of a day. With `download`, the input and description are downloaded and the example is
+/// extracted from the description. Existing files are kept, so scaffolding a day again only fills
+/// in what is missing.
+pub fn handle(puzzle: PuzzleId, template: &str, answer: &str, download: bool) {
+    let module_path = puzzle.bin_path();
+    let input_path = puzzle.data_path("inputs", "txt");
+    let puzzle_path = puzzle.data_path("puzzles", "md");
+    let example_path = puzzle.data_path("examples", "txt");
+    let examples_path = examples::get_path(puzzle);
+
+    let downloaded = if download {
+        Some(download::download(puzzle, false))
+    } else {
+        None
+    };
+
+    // the title of the puzzle is only known once the description was downloaded.
+    let module = if Path::new(&module_path).exists() {
+        Status::Exists
+    } else {
+        match scaffold_templates::load(template) {
+            Ok(template) => {
+                let module =
+                    scaffold_templates::render(&template, &Placeholders::new(puzzle, answer));
+                create_new(&module_path, &module).into()
+            }
+            Err(e) => Status::Failed(e.to_string()),
+        }
+    };
+
+    let input = match downloaded {
+        Some(Ok(Downloaded { input: true, .. })) => Status::Downloaded,
+        _ => create_new(&input_path, "").into(),
+    };
+
+    let description = match &downloaded {
+        Some(Ok(Downloaded {
+            description: true, ..
+        })) => Status::Downloaded,
+        Some(Err(e)) => Status::Failed(e.clone()),
+        _ if Path::new(&puzzle_path).exists() => Status::Exists,
+        _ => Status::Missing,
+    };
+
+    let (example, answers) = scaffold_example(puzzle, &example_path);
+    let examples_file = scaffold_examples_file(puzzle, &examples_path, answers.as_ref()).into();
+
+    let rows = [
+        ("module", &module_path, module),
+        ("input", &input_path, input),
+        ("puzzle", &puzzle_path, description),
+        ("example", &example_path, example),
+        ("answers", &examples_path, examples_file),
+    ];
+
+    println!("{ANSI_BOLD}{puzzle}{ANSI_RESET}");
+    for (name, path, status) in &rows {
+        println!("  {name:<8} {path:<28} {status}");
+    }
+
+    println!("---");
+
+    if rows
+        .iter()
+        .any(|(_, _, status)| matches!(status, Status::Failed(_)))
+    {
+        process::exit(1);
     }
+
+    println!(
+        "🎄 Type `cargo solve {} --year {}` to run your solution.",
+        puzzle.day, puzzle.year
+    );
 }
-"#;
 
-fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create_new(true).open(path)
+/// Fills an empty or missing example file with an example from the description. Returns the
+/// expected answers of the example, if it was extracted.
+fn scaffold_example(puzzle: PuzzleId, path: &str) -> (Status, Option<Answers>) {
+    let status = match create_new(path, "") {
+        Ok(Status::Empty) => Status::Empty,
+        status => return (status.into(), None),
+    };
+
+    let Ok(description) = fs::read_to_string(puzzle.data_path("puzzles", "md")) else {
+        return (status, None);
+    };
+
+    let blocks = markdown::code_blocks(&description);
+
+    match pick_example(&blocks) {
+        Some((index, block)) => match fs::write(path, &block.content) {
+            Ok(()) => (Status::Extracted(index + 1), Some(block.answers.clone())),
+            Err(e) => (Status::Failed(e.to_string()), None),
+        },
+        None => (status, None),
+    }
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+/// The first code block of part one with an expected answer, or the first block.
+fn pick_example(blocks: &[CodeBlock]) -> Option<(usize, &CodeBlock)> {
+    blocks
+        .iter()
+        .enumerate()
+        .find(|(_, block)| block.part == 1 && block.answers.part_one.is_some())
+        .or_else(|| blocks.first().map(|block| (0, block)))
 }
 
-pub fn handle(day: Day) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Creates the examples file. A file that was not edited since it was scaffolded is filled in
+/// with the expected answers of an extracted example.
+fn scaffold_examples_file(
+    puzzle: PuzzleId,
+    path: &str,
+    answers: Option<&Answers>,
+) -> io::Result<Status> {
+    let file = format!("{}.txt", puzzle.day);
+    let skeleton = EXAMPLES_TEMPLATE.replace("DAY_FILE", &file);
 
-    let mut file = match safe_create_file(&module_path) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
-        }
+    let Some(answers) = answers.filter(|answers| **answers != Answers::default()) else {
+        return create_new(path, &skeleton);
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("DAY_NUMBER", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
-        }
-    }
+    let content = examples::to_toml(&[Case {
+        name: file.clone(),
+        file: Some(file),
+        input: String::new(),
+        answers: answers.clone(),
+    }]);
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
+    match fs::read_to_string(path) {
+        Ok(existing) if existing == skeleton => {
+            fs::write(path, content)?;
+            Ok(Status::Updated)
         }
+        Ok(_) => Ok(Status::Exists),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => create_new(path, &content),
+        Err(e) => Err(e),
     }
+}
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::pick_example;
+    use crate::template::answers::Answers;
+    use crate::template::markdown::CodeBlock;
+
+    fn block(part: u8, part_one: Option<&str>) -> CodeBlock {
+        CodeBlock {
+            part,
+            content: String::new(),
+            answers: Answers {
+                part_one: part_one.map(String::from),
+                part_two: None,
+            },
         }
     }
 
-    println!("---");
-    println!("🎄 Type `cargo solve {}` to run your solution.", day);
+    #[test]
+    fn picks_examples_with_answers() {
+        let blocks = [block(1, None), block(1, Some("42")), block(2, None)];
+        assert_eq!(pick_example(&blocks).map(|(index, _)| index), Some(1));
+
+        let blocks = [block(1, None), block(2, None)];
+        assert_eq!(pick_example(&blocks).map(|(index, _)| index), Some(0));
+
+        assert!(pick_example(&[]).is_none());
+    }
 }
@@ -1,31 +1,39 @@
-use std::process::{Command, Stdio};
-
-use crate::Day;
-
-pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
-
-    if release {
-        cmd_args.push("--release".to_string());
-    }
-
-    cmd_args.push("--".to_string());
-
-    if let Some(submit_part) = submit_part {
-        cmd_args.push("--submit".to_string());
-        cmd_args.push(submit_part.to_string());
-    }
+use std::process;
+
+use crate::template::cli::RunConfig;
+use crate::template::registry::{self, Entry};
+use crate::template::report;
+use crate::template::supervisor;
+use crate::PuzzleId;
+
+pub fn handle(solutions: &[Entry], puzzle: PuzzleId, config: &RunConfig) {
+    let Some(solution) = registry::find(solutions, puzzle) else {
+        eprintln!(
+            "No solution found for {puzzle}. Type `cargo scaffold {} --year {}` to create one.",
+            puzzle.day, puzzle.year
+        );
+        process::exit(1);
+    };
+
+    run(solution, config);
+}
 
-    if time {
-        cmd_args.push("--time".to_string());
+/// Runs a solution as configured on the command line, by `cargo solve` or its own binary.
+pub fn run(solution: &Entry, config: &RunConfig) {
+    // answers for any other input would be wrong for the puzzle.
+    if config.options.submit.is_some() && !config.input.is_puzzle() {
+        eprintln!("Answers can only be submitted for the puzzle input.");
+        process::exit(1);
     }
 
-    let mut cmd = Command::new("cargo")
-        .args(&cmd_args)
-        .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit())
-        .spawn()
-        .unwrap();
+    let input = match config.input.read(solution.puzzle) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("Could not read {}: {e}", config.input);
+            process::exit(1);
+        }
+    };
 
-    cmd.wait().unwrap();
+    let report = supervisor::run(solution, &input, &config.options);
+    report::print_collected(&[report], config.options.format);
 }
@@ -0,0 +1,85 @@
+use std::{fs, path::Path, process};
+
+use crate::template::config;
+use crate::template::leaderboard::{self, Leaderboard};
+use crate::Year;
+
+/// Shows the stars of a private leaderboard and writes those of the user to the readme.
+///
+/// The leaderboard is read from `file` if given, otherwise it is downloaded. Its id defaults to
+/// the configured `leaderboard_id`, then to the id of the user, which is `user_id` unless given.
+pub fn handle(year: Year, file: Option<&Path>, id: Option<String>, user: Option<String>) {
+    let config = config::get();
+    let user = user.or_else(|| config.user_id.clone());
+
+    let content = match file {
+        Some(file) => fs::read_to_string(file).map_err(|e| e.to_string()),
+        None => match id
+            .or_else(|| config.leaderboard_id.clone())
+            .or_else(|| user.clone())
+        {
+            Some(id) => fetch(year, &id),
+            None => Err("pass --id, or set `leaderboard_id` or `user_id` in aoc.toml.".into()),
+        },
+    };
+
+    let leaderboard = match content.and_then(|c| leaderboard::parse(&c).map_err(|e| e.to_string()))
+    {
+        Ok(leaderboard) => leaderboard,
+        Err(e) => {
+            eprintln!("Could not load leaderboard: {e}");
+            process::exit(1);
+        }
+    };
+
+    if leaderboard.year != year {
+        eprintln!(
+            "Note: the leaderboard is for {}, not {year}.",
+            leaderboard.year
+        );
+    }
+
+    println!("{}", leaderboard::render(&leaderboard));
+    update_readme(&leaderboard, user.as_deref());
+}
+
+fn update_readme(leaderboard: &Leaderboard, user: Option<&str>) {
+    let member = match (user, leaderboard.members.as_slice()) {
+        (Some(user), _) => leaderboard.member(user),
+        // a leaderboard of one is the user's own.
+        (None, [member]) => Some(member),
+        (None, _) => None,
+    };
+
+    let Some(member) = member else {
+        match user {
+            Some(user) => eprintln!("User #{user} is not a member of the leaderboard."),
+            None => eprintln!(
+                "Pass --user or set `user_id` in aoc.toml to write your stars to the readme."
+            ),
+        }
+        return;
+    };
+
+    match leaderboard::update_readme(leaderboard.year, member) {
+        Ok(()) => println!(
+            "Successfully updated README with the stars of {}.",
+            member.display_name()
+        ),
+        Err(e) => eprintln!("Failed to update readme with stars: {e}"),
+    }
+}
+
+#[cfg(not(feature = "client"))]
+fn fetch(_year: Year, _id: &str) -> Result<String, String> {
+    Err("downloading leaderboards requires the `client` feature, pass --file instead.".into())
+}
+
+#[cfg(feature = "client")]
+fn fetch(year: Year, id: &str) -> Result<String, String> {
+    use crate::template::aoc_client::Client;
+
+    Client::from_env()
+        .and_then(|client| client.leaderboard(year, id))
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,241 @@
+use std::{
+    env, fs,
+    process::{Command, Stdio},
+    time::Duration,
+};
+
+use crate::template::{
+    config, examples, json, readme_benchmarks,
+    registry::{self, Entry},
+    report::{OutputFormat, PartReport, PartStatus, RunReport},
+    runner::RunOptions,
+    supervisor, try_read_file, ANSI_BOLD, ANSI_GREEN, ANSI_RED, ANSI_RESET,
+};
+use crate::{all_days, PuzzleId, Year};
+
+/// Parts that run longer than this are reported as timed out, unless `--timeout` is passed.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The progress of a single day.
+struct DayStatus {
+    puzzle: PuzzleId,
+    name: Option<&'static str>,
+    has_solution: bool,
+    has_input: bool,
+    has_example: bool,
+    has_description: bool,
+    /// Present if the solution could be run against the puzzle input.
+    report: Option<RunReport>,
+    /// The medians of the parse step and both parts in the readme benchmarks.
+    medians: Option<[Option<String>; 3]>,
+    /// Present if the tests of the solution were run.
+    tests_passed: Option<bool>,
+}
+
+impl DayStatus {
+    fn part(&self, part: u8) -> Option<&PartReport> {
+        self.report
+            .as_ref()?
+            .parts
+            .iter()
+            .find(|report| report.part == part)
+    }
+
+    fn to_json(&self) -> String {
+        let parts: Vec<String> = self
+            .report
+            .iter()
+            .flat_map(|report| &report.parts)
+            .map(|part| {
+                format!(
+                    r#"{{"part":{},"status":"{}","answer":{}}}"#,
+                    part.part,
+                    part.status(),
+                    part.answer
+                        .as_deref()
+                        .map_or_else(|| "null".into(), json::encode_string)
+                )
+            })
+            .collect();
+
+        let median = |index: usize| {
+            self.medians
+                .as_ref()
+                .and_then(|medians| medians[index].as_deref())
+                .map_or_else(|| "null".into(), json::encode_string)
+        };
+
+        format!(
+            r#"{{"year":{},"day":{},"solution":{},"input":{},"example":{},"puzzle":{},"parts":[{}],"readme":{{"parse":{},"part_one":{},"part_two":{}}},"tests":{}}}"#,
+            self.puzzle.year.into_inner(),
+            self.puzzle.day.into_inner(),
+            self.has_solution,
+            self.has_input,
+            self.has_example,
+            self.has_description,
+            parts.join(","),
+            median(0),
+            median(1),
+            median(2),
+            self.tests_passed
+                .map_or_else(|| "null".into(), |passed| passed.to_string())
+        )
+    }
+}
+
+/// Shows the progress of every day of a year: which files exist, what the parts answer, the
+/// timings in the readme and, if `run_tests` is set, whether the tests pass.
+pub fn handle(
+    solutions: &[Entry],
+    year: Year,
+    format: OutputFormat,
+    timeout: Option<Duration>,
+    run_tests: bool,
+) {
+    let options = RunOptions {
+        time: false,
+        submit: None,
+        format,
+        quiet: true,
+        timeout: Some(timeout.unwrap_or(DEFAULT_TIMEOUT)),
+        part: None,
+    };
+
+    let medians = fs::read_to_string(&config::get().readme)
+        .ok()
+        .and_then(|readme| readme_benchmarks::recorded_medians(&readme, year).ok())
+        .unwrap_or_default();
+
+    let days: Vec<DayStatus> = all_days()
+        .map(|day| {
+            let puzzle = PuzzleId::new(year, day);
+            let solution = registry::find(solutions, puzzle);
+            let input = try_read_file("inputs", puzzle)
+                .ok()
+                .filter(|i| !i.is_empty());
+
+            DayStatus {
+                puzzle,
+                name: solution.and_then(|solution| solution.name),
+                has_solution: solution.is_some(),
+                has_input: input.is_some(),
+                has_example: has_example(puzzle),
+                has_description: is_present(&puzzle.data_path("puzzles", "md")),
+                report: solution.zip(input).map(|(solution, input)| {
+                    supervisor::run_buffered(solution, &input, &options).0
+                }),
+                medians: medians.get(&day).cloned(),
+                tests_passed: solution
+                    .filter(|_| run_tests)
+                    .map(|_| run_bin_tests(puzzle)),
+            }
+        })
+        .collect();
+
+    match format {
+        OutputFormat::Text => print_table(&days),
+        OutputFormat::Json => {
+            let items: Vec<String> = days.iter().map(DayStatus::to_json).collect();
+            println!("[{}]", items.join(","));
+        }
+        OutputFormat::Jsonl => {
+            for day in &days {
+                println!("{}", day.to_json());
+            }
+        }
+    }
+}
+
+fn is_present(path: &str) -> bool {
+    fs::metadata(path).is_ok_and(|metadata| metadata.len() > 0)
+}
+
+/// Whether a day has an example file, or a case with input in its examples file.
+fn has_example(puzzle: PuzzleId) -> bool {
+    is_present(&puzzle.data_path("examples", "txt"))
+        || examples::load(puzzle).is_ok_and(|cases| cases.iter().any(|case| !case.input.is_empty()))
+}
+
+/// Runs the unit tests of a solution binary.
+fn run_bin_tests(puzzle: PuzzleId) -> bool {
+    Command::new(env::var("CARGO").unwrap_or_else(|_| "cargo".into()))
+        .args(["test", "--quiet", "--bin", &puzzle.bin_name()])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+fn print_table(days: &[DayStatus]) {
+    println!(
+        "{ANSI_BOLD}{:<4} {:<3} {:<5} {:<7} {:<6} {:<16} {:<16} {:<21} Tests{ANSI_RESET}",
+        "Day", "Bin", "Input", "Example", "Puzzle", "Part 1", "Part 2", "README"
+    );
+
+    for day in days {
+        let check = |is_present: bool, width: usize| {
+            if is_present {
+                format!("{ANSI_GREEN}{:<width$}{ANSI_RESET}", "✓")
+            } else {
+                format!("{:<width$}", "·")
+            }
+        };
+
+        let readme = match &day.medians {
+            Some([_, part_one, part_two]) => format!(
+                "{} / {}",
+                part_one.as_deref().unwrap_or("-"),
+                part_two.as_deref().unwrap_or("-")
+            ),
+            None => "-".into(),
+        };
+
+        let tests = match day.tests_passed {
+            Some(true) => format!("{ANSI_GREEN}passed{ANSI_RESET}"),
+            Some(false) => format!("{ANSI_RED}failed{ANSI_RESET}"),
+            None => "-".into(),
+        };
+
+        println!(
+            "{:<4} {} {} {} {} {} {} {:<21} {tests}",
+            day.puzzle.day,
+            check(day.has_solution, 3),
+            check(day.has_input, 5),
+            check(day.has_example, 7),
+            check(day.has_description, 6),
+            part_cell(day.part(1), 16),
+            part_cell(day.part(2), 16),
+            readme,
+        );
+    }
+
+    let names: Vec<String> = days
+        .iter()
+        .filter_map(|day| day.name.map(|name| format!("{}: {name}", day.puzzle.day)))
+        .collect();
+
+    if !names.is_empty() {
+        println!("\n{}", names.join(", "));
+    }
+}
+
+/// The answer of a solved part, or why it has none.
+fn part_cell(report: Option<&PartReport>, width: usize) -> String {
+    let Some(report) = report else {
+        return format!("{:<width$}", "-");
+    };
+
+    match (report.status(), &report.answer) {
+        (PartStatus::Solved, Some(answer)) => {
+            let answer = answer.lines().next().unwrap_or_default();
+            let answer: String = if answer.chars().count() > width {
+                answer.chars().take(width - 1).chain(['…']).collect()
+            } else {
+                answer.to_string()
+            };
+            format!("{ANSI_GREEN}{answer:<width$}{ANSI_RESET}")
+        }
+        (PartStatus::Unsolved, _) => format!("{:<width$}", "unsolved"),
+        (status, _) => format!("{ANSI_RED}{:<width$}{ANSI_RESET}", status.to_string()),
+    }
+}
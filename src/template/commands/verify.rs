@@ -0,0 +1,192 @@
+use std::{process, time::Duration};
+
+use crate::template::{
+    answers::{self, Answers},
+    commands::status::DEFAULT_TIMEOUT,
+    registry::{self, Entry},
+    report::{OutputFormat, PartReport},
+    runner::RunOptions,
+    supervisor, try_read_file, ANSI_BOLD, ANSI_RESET,
+};
+use crate::{all_days, Day, PuzzleId, Year};
+
+/// The outcome of comparing a part against its stored answer.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail {
+        expected: String,
+        actual: Option<String>,
+    },
+    Missing,
+}
+
+/// Compares the parts of one or every day against their stored answers. Parts that run longer than
+/// `timeout`, or [`DEFAULT_TIMEOUT`] if not given, are reported as timed out.
+pub fn handle(solutions: &[Entry], year: Year, day: Option<Day>, timeout: Option<Duration>) {
+    let is_single_day = day.is_some();
+    let days: Vec<Day> = match day {
+        Some(day) => vec![day],
+        None => all_days().collect(),
+    };
+
+    let options = RunOptions {
+        quiet: true,
+        format: OutputFormat::Text,
+        timeout: Some(timeout.unwrap_or(DEFAULT_TIMEOUT)),
+        ..RunOptions::default()
+    };
+
+    let mut failures = 0;
+
+    for day in days {
+        let puzzle = PuzzleId::new(year, day);
+
+        let Some(solution) = registry::find(solutions, puzzle) else {
+            // only complain about missing solutions if the day was requested explicitly.
+            if is_single_day {
+                eprintln!("No solution found for {puzzle}.");
+                process::exit(1);
+            }
+            continue;
+        };
+
+        let expected = match answers::load(puzzle) {
+            Ok(answers) => answers,
+            Err(e) => {
+                eprintln!("{puzzle}: {e}");
+                failures += 1;
+                continue;
+            }
+        };
+
+        // a missing input is not a mismatch, so it is reported like a missing answer, unless the
+        // day was requested explicitly.
+        let Ok(input) = try_read_file("inputs", puzzle) else {
+            if is_single_day {
+                eprintln!("No input found for {puzzle}.");
+                process::exit(1);
+            }
+            println!("Day {day}: ? missing input");
+            continue;
+        };
+
+        for report in supervisor::run(solution, &input, &options).parts {
+            let verdict = verify_part(&report, &expected);
+            print_verdict(&report, &verdict);
+
+            if matches!(verdict, Verdict::Fail { .. }) {
+                failures += 1;
+            }
+        }
+    }
+
+    if failures > 0 {
+        eprintln!("\n{ANSI_BOLD}{failures} part(s) failed verification.{ANSI_RESET}");
+        process::exit(1);
+    }
+}
+
+#[must_use]
+pub fn verify_part(report: &PartReport, answers: &Answers) -> Verdict {
+    match answers.get(report.part) {
+        None => Verdict::Missing,
+        Some(expected) if report.answer.as_deref() == Some(expected) => Verdict::Pass,
+        Some(expected) => Verdict::Fail {
+            expected: expected.to_string(),
+            actual: report.answer.clone(),
+        },
+    }
+}
+
+fn print_verdict(report: &PartReport, verdict: &Verdict) {
+    let label = format!("Day {} Part {}", report.puzzle.day, report.part);
+
+    match verdict {
+        Verdict::Pass => println!("{label}: ✔ pass"),
+        Verdict::Missing => println!("{label}: ? missing answer"),
+        Verdict::Fail { expected, .. } => println!(
+            "{label}: ✖ {ANSI_BOLD}fail{ANSI_RESET} (expected {expected}, {})",
+            outcome(report)
+        ),
+    }
+}
+
+/// What a part returned, or why it returned nothing.
+fn outcome(report: &PartReport) -> String {
+    match (&report.answer, &report.error) {
+        (Some(answer), _) => format!("got {answer}"),
+        (None, Some(error)) => format!("{}: {error}", report.status()),
+        (None, None) => report.status().to_string(),
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{outcome, verify_part, Verdict};
+    use crate::template::{answers::Answers, report::PartReport, solution::Error};
+    use crate::{day, year, PuzzleId};
+
+    fn report(part: u8, answer: Option<&str>) -> PartReport {
+        PartReport {
+            puzzle: PuzzleId::new(year!(2023), day!(1)),
+            part,
+            answer: answer.map(String::from),
+            error: None,
+            duration: Duration::from_nanos(100),
+            samples: 1,
+            stats: None,
+        }
+    }
+
+    #[test]
+    fn verifies_parts() {
+        let answers = Answers {
+            part_one: Some("142".into()),
+            part_two: None,
+        };
+
+        assert_eq!(
+            verify_part(&report(1, Some("142")), &answers),
+            Verdict::Pass
+        );
+        assert_eq!(
+            verify_part(&report(1, Some("143")), &answers),
+            Verdict::Fail {
+                expected: "142".into(),
+                actual: Some("143".into())
+            }
+        );
+        assert_eq!(
+            verify_part(&report(1, None), &answers),
+            Verdict::Fail {
+                expected: "142".into(),
+                actual: None
+            }
+        );
+        assert_eq!(
+            verify_part(&report(2, Some("1")), &answers),
+            Verdict::Missing
+        );
+    }
+
+    #[test]
+    fn describes_outcomes() {
+        assert_eq!(outcome(&report(1, Some("143"))), "got 143");
+        assert_eq!(outcome(&report(1, None)), "unsolved");
+
+        let timed_out = PartReport {
+            error: Some(Error::TimedOut(Duration::from_secs(10))),
+            ..report(2, None)
+        };
+        assert_eq!(outcome(&timed_out), "timed_out: timed out after 10.0s");
+
+        let crashed = PartReport {
+            error: Some(Error::Panicked("index out of bounds".into())),
+            ..report(2, None)
+        };
+        assert_eq!(outcome(&crashed), "crashed: panicked: index out of bounds");
+    }
+}
@@ -0,0 +1,338 @@
+/// Settings of the template, read from `aoc.toml` in the working directory.
+///
+/// The file uses the same subset of TOML as the [answers](crate::template::answers) files, plus
+/// a `[bench]` table. Every setting is optional and can be overridden by an environment variable:
+///
+/// ```toml
+/// year = 2023             # AOC_YEAR
+/// data_dir = "data"       # AOC_DATA_DIR
+/// readme = "README.md"    # AOC_README
+/// format = "text"         # AOC_FORMAT: "text", "json" or "jsonl"
+/// color = "auto"          # AOC_COLOR: "auto", "always" or "never"
+/// session = "53616c74..." # AOC_SESSION, else ~/.adventofcode.session
+/// base_url = "https://adventofcode.com" # AOC_BASE_URL
+/// leaderboard_id = "3031" # AOC_LEADERBOARD_ID
+/// user_id = "3031"        # AOC_USER_ID
+///
+/// [bench]
+/// budget_ms = 1000        # AOC_BENCH_BUDGET_MS
+/// min_iterations = 10     # AOC_BENCH_MIN_ITERATIONS
+/// max_iterations = 10000  # AOC_BENCH_MAX_ITERATIONS
+/// ```
+use std::{
+    env, fs,
+    io::{self, IsTerminal},
+    str::FromStr,
+    sync::OnceLock,
+    time::Duration,
+};
+
+use crate::template::answers::{self, Error};
+use crate::template::report::OutputFormat;
+use crate::Year;
+
+pub const CONFIG_PATH: &str = "aoc.toml";
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// When output is colored.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Color {
+    /// Only if stdout is a terminal and `NO_COLOR` is not set.
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl FromStr for Color {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(Self::Auto),
+            "always" => Ok(Self::Always),
+            "never" => Ok(Self::Never),
+            _ => Err(format!(
+                "invalid color `{s}`, expected auto, always or never"
+            )),
+        }
+    }
+}
+
+/// How long parts are benchmarked by `--time`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bench {
+    /// The time the iterations of a part should take in total.
+    pub budget: Duration,
+    pub min_iterations: u128,
+    pub max_iterations: u128,
+}
+
+impl Default for Bench {
+    fn default() -> Self {
+        Self {
+            budget: Duration::from_secs(1),
+            min_iterations: 10,
+            max_iterations: 10000,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    /// The default year of commands, if `--year` is not passed.
+    pub year: Option<Year>,
+    /// The folder of inputs, examples, answers and caches.
+    pub data_dir: String,
+    /// The readme that benchmarks and stars are written to.
+    pub readme: String,
+    /// The default output format of commands that run solutions.
+    pub format: OutputFormat,
+    pub color: Color,
+    /// The session cookie of the website. If unset, it is read from the files aoc-cli uses.
+    pub session: Option<String>,
+    /// The website the client talks to, e.g. a local server in tests.
+    pub base_url: String,
+    /// The private leaderboard of `stars`, if not the one of the user.
+    pub leaderboard_id: Option<String>,
+    /// The user whose stars are written to the readme.
+    pub user_id: Option<String>,
+    pub bench: Bench,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            year: None,
+            data_dir: "data".into(),
+            readme: "README.md".into(),
+            format: OutputFormat::default(),
+            color: Color::default(),
+            session: None,
+            base_url: DEFAULT_BASE_URL.into(),
+            leaderboard_id: None,
+            user_id: None,
+            bench: Bench::default(),
+        }
+    }
+}
+
+impl Config {
+    /// Reads `aoc.toml` if it exists and applies the environment variables on top.
+    pub fn load() -> Result<Self, Error> {
+        let mut config = match fs::read_to_string(CONFIG_PATH) {
+            Ok(content) => parse(&content)?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Config::default(),
+            Err(e) => return Err(Error::IO(CONFIG_PATH, e)),
+        };
+
+        config.apply_env(|key| env::var(key).ok())?;
+        Ok(config)
+    }
+
+    /// The path of a file in the data directory.
+    #[must_use]
+    pub fn data_path(&self, path: &str) -> String {
+        format!("{}/{path}", self.data_dir)
+    }
+
+    /// Whether output should be colored.
+    #[must_use]
+    pub fn use_color(&self) -> bool {
+        match self.color {
+            Color::Auto => env::var_os("NO_COLOR").is_none() && io::stdout().is_terminal(),
+            Color::Always => true,
+            Color::Never => false,
+        }
+    }
+
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        match key {
+            "year" => self.year = Some(parse_setting(key, value)?),
+            "data_dir" => self.data_dir = value.to_string(),
+            "readme" => self.readme = value.to_string(),
+            "format" => self.format = parse_setting(key, value)?,
+            "color" => self.color = value.parse()?,
+            "session" => self.session = Some(value.trim().to_string()),
+            "base_url" => self.base_url = value.trim_end_matches('/').to_string(),
+            "leaderboard_id" => self.leaderboard_id = Some(value.to_string()),
+            "user_id" => self.user_id = Some(value.to_string()),
+            "bench.budget_ms" => {
+                self.bench.budget = Duration::from_millis(parse_setting(key, value)?);
+            }
+            "bench.min_iterations" => self.bench.min_iterations = parse_setting(key, value)?,
+            "bench.max_iterations" => self.bench.max_iterations = parse_setting(key, value)?,
+            key => return Err(format!("unknown key `{key}`")),
+        }
+
+        Ok(())
+    }
+
+    /// Overrides settings with the environment variables `var` returns.
+    fn apply_env(&mut self, var: impl Fn(&str) -> Option<String>) -> Result<(), Error> {
+        const VARS: &[(&str, &str)] = &[
+            ("AOC_YEAR", "year"),
+            ("AOC_DATA_DIR", "data_dir"),
+            ("AOC_README", "readme"),
+            ("AOC_FORMAT", "format"),
+            ("AOC_COLOR", "color"),
+            ("AOC_SESSION", "session"),
+            ("AOC_BASE_URL", "base_url"),
+            ("AOC_LEADERBOARD_ID", "leaderboard_id"),
+            ("AOC_USER_ID", "user_id"),
+            ("AOC_BENCH_BUDGET_MS", "bench.budget_ms"),
+            ("AOC_BENCH_MIN_ITERATIONS", "bench.min_iterations"),
+            ("AOC_BENCH_MAX_ITERATIONS", "bench.max_iterations"),
+        ];
+
+        for (name, key) in VARS {
+            if let Some(value) = var(name) {
+                self.set(key, &value)
+                    .map_err(|e| Error::Parser(CONFIG_PATH, format!("{name}: {e}")))?;
+            }
+        }
+
+        self.validate()
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        if self.bench.min_iterations == 0 || self.bench.min_iterations > self.bench.max_iterations {
+            return Err(Error::Parser(
+                CONFIG_PATH,
+                "bench iterations need 0 < min_iterations <= max_iterations".into(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// The configuration of this process. It is loaded once; if it can not be loaded, a warning is
+/// printed and the defaults are used.
+pub fn get() -> &'static Config {
+    static CONFIG: OnceLock<Config> = OnceLock::new();
+
+    CONFIG.get_or_init(|| {
+        Config::load().unwrap_or_else(|e| {
+            eprintln!("Warning: {e}, using the default settings.");
+            Config::default()
+        })
+    })
+}
+
+fn parse_setting<T: FromStr>(key: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid value `{value}` for `{key}`"))
+}
+
+fn parse(content: &str) -> Result<Config, Error> {
+    let mut config = Config::default();
+    let mut table = "";
+
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        let error =
+            |message: &str| Error::Parser(CONFIG_PATH, format!("line {}: {message}", index + 1));
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some(name) = line.strip_prefix('[') {
+            table = match name.split('#').next().unwrap_or_default().trim() {
+                "bench]" => "bench.",
+                _ => return Err(error("unknown table")),
+            };
+            continue;
+        }
+
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| error("expected `key = value`"))?;
+
+        let value = answers::parse_value(value.trim()).ok_or_else(|| error("invalid value"))?;
+
+        config
+            .set(&format!("{table}{}", key.trim()), &value)
+            .map_err(|e| error(&e))?;
+    }
+
+    config.validate()?;
+    Ok(config)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{parse, Color, Config};
+    use crate::template::report::OutputFormat;
+    use crate::year;
+
+    #[test]
+    fn parses_config() {
+        let config = parse(
+            r#"
+# settings of the template
+year = 2022
+data_dir = "puzzles" # relative to the repository
+format = "jsonl"
+base_url = "http://localhost:8080/"
+user_id = "3031"
+
+[bench]
+budget_ms = 250
+max_iterations = 500
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config,
+            Config {
+                year: Some(year!(2022)),
+                data_dir: "puzzles".into(),
+                format: OutputFormat::Jsonl,
+                base_url: "http://localhost:8080".into(),
+                user_id: Some("3031".into()),
+                bench: super::Bench {
+                    budget: Duration::from_millis(250),
+                    min_iterations: 10,
+                    max_iterations: 500,
+                },
+                ..Config::default()
+            }
+        );
+
+        assert!(parse("colour = \"never\"").is_err());
+        assert!(parse("[bench]\nmin_iterations = 0").is_err());
+        assert!(parse("[benchmarks]").is_err());
+    }
+
+    #[test]
+    fn applies_env_overrides() {
+        let mut config = parse("year = 2022\ncolor = \"always\"\nuser_id = \"3031\"").unwrap();
+
+        config
+            .apply_env(|name| match name {
+                "AOC_YEAR" => Some("2023".into()),
+                "AOC_README" => Some("docs/README.md".into()),
+                "AOC_SESSION" => Some("53616c74\n".into()),
+                "AOC_USER_ID" => Some("4242".into()),
+                _ => None,
+            })
+            .unwrap();
+
+        assert_eq!(config.year, Some(year!(2023)));
+        assert_eq!(config.readme, "docs/README.md");
+        assert_eq!(config.color, Color::Always);
+        assert_eq!(config.session.as_deref(), Some("53616c74"));
+        assert_eq!(config.user_id.as_deref(), Some("4242"));
+        assert_eq!(config.leaderboard_id, None);
+
+        assert!(config
+            .apply_env(|name| (name == "AOC_FORMAT").then(|| "yaml".into()))
+            .is_err());
+    }
+}
@@ -0,0 +1,323 @@
+/// Example cases with expected answers, stored per puzzle in `data/<year>/examples/<day>.toml`.
+///
+/// Each `[[case]]` either references an example file relative to the examples folder, or embeds
+/// its input as a multi-line literal string. Like in TOML, a newline right after the opening
+/// quotes is trimmed. Expected answers use the same values as the answers files.
+///
+/// ```toml
+/// [[case]]
+/// name = "repeat instructions"
+/// file = "08-1.txt"
+/// part_one = 6
+///
+/// [[case]]
+/// input = '''
+/// LR
+///
+/// 11A = (11B, XXX)
+/// '''
+/// part_two = 6
+/// ```
+use std::{env, fs, io};
+
+use crate::template::answers::{self, Answers, Error};
+use crate::template::config;
+use crate::template::solution::IntoAnswer;
+use crate::PuzzleId;
+
+/// The name of examples files in error messages.
+const FILE: &str = "examples file";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Case {
+    /// The `name` of the case, its `file` or its position, e.g. `case 2`.
+    pub name: String,
+    /// The example file the input was read from, relative to the examples folder.
+    pub file: Option<String>,
+    pub input: String,
+    pub answers: Answers,
+}
+
+impl Case {
+    #[must_use]
+    pub fn to_toml(&self) -> String {
+        let mut toml = String::from("[[case]]\n");
+
+        // cases are named after their file by default.
+        if self.file.as_ref().is_none_or(|file| *file != self.name) {
+            toml += &format!("name = {}\n", answers::format_value(&self.name));
+        }
+
+        match &self.file {
+            Some(file) => toml += &format!("file = {}\n", answers::format_value(file)),
+            None => toml += &format!("input = '''\n{}'''\n", self.input),
+        }
+
+        toml + &self.answers.to_toml()
+    }
+}
+
+/// Serializes cases to the format of the examples file.
+#[must_use]
+pub fn to_toml(cases: &[Case]) -> String {
+    cases
+        .iter()
+        .map(Case::to_toml)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[must_use]
+pub fn get_path(puzzle: PuzzleId) -> String {
+    puzzle.data_path("examples", "toml")
+}
+
+/// Loads the example cases for a puzzle. A missing file yields no cases.
+pub fn load(puzzle: PuzzleId) -> Result<Vec<Case>, Error> {
+    let content = match fs::read_to_string(get_path(puzzle)) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(Error::IO(FILE, e)),
+    };
+
+    let examples_dir = env::current_dir()
+        .map_err(|e| Error::IO(FILE, e))?
+        .join(config::get().data_path(&format!("{}/examples", puzzle.year)));
+    parse(&content, |file| fs::read_to_string(examples_dir.join(file)))
+}
+
+/// Runs a part against every example case that has an expected answer for it.
+///
+/// Meant for tests, so it panics on the first mismatch, if the examples can not be loaded or if
+/// no case has an expected answer for the part:
+///
+/// ```ignore
+/// #[test]
+/// fn test_part_one() {
+///     advent_of_code::template::examples::check(PUZZLE, 1, part_one);
+/// }
+/// ```
+#[track_caller]
+pub fn check<R: IntoAnswer>(puzzle: PuzzleId, part: u8, solve: impl Fn(&str) -> R) {
+    let cases = load(puzzle).unwrap_or_else(|e| panic!("{e}"));
+    check_cases(puzzle, &cases, part, solve);
+}
+
+/// Runs a part against the cases of a puzzle with an expected answer for it, see [`check`].
+#[track_caller]
+fn check_cases<R: IntoAnswer>(
+    puzzle: PuzzleId,
+    cases: &[Case],
+    part: u8,
+    solve: impl Fn(&str) -> R,
+) {
+    let mut checked = 0;
+
+    for case in cases {
+        let Some(expected) = case.answers.get(part) else {
+            continue;
+        };
+
+        match solve(&case.input).into_answer() {
+            Ok(answer) => assert_eq!(
+                answer.to_string(),
+                expected,
+                "wrong answer for part {part} of example `{}`",
+                case.name
+            ),
+            Err(e) => panic!("part {part} of example `{}` failed: {e}", case.name),
+        }
+
+        checked += 1;
+    }
+
+    assert!(
+        checked > 0,
+        "no example of {puzzle} has an expected answer for part {part}, add one to {}",
+        get_path(puzzle)
+    );
+}
+
+/// Parses the cases of an examples file. `read_example` reads the example files cases refer to.
+fn parse(
+    content: &str,
+    read_example: impl Fn(&str) -> io::Result<String>,
+) -> Result<Vec<Case>, Error> {
+    let mut cases: Vec<Case> = vec![];
+    let mut lines = content.lines().enumerate();
+
+    while let Some((index, line)) = lines.next() {
+        let line = line.trim();
+        let error = |message: &str| Error::Parser(FILE, format!("line {}: {message}", index + 1));
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if line == "[[case]]" {
+            cases.push(Case::default());
+            continue;
+        }
+
+        let Some(case) = cases.last_mut() else {
+            return Err(error("expected `[[case]]`"));
+        };
+
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| error("expected `key = value`"))?;
+        let (key, value) = (key.trim(), value.trim());
+
+        if key == "input" {
+            let literal = value
+                .strip_prefix("'''")
+                .ok_or_else(|| error("expected a multi-line string"))?;
+
+            case.input = match literal.split_once("'''") {
+                Some((input, _)) => input.to_string(),
+                None => {
+                    // the newline after the opening quotes is trimmed.
+                    let mut input = if literal.is_empty() {
+                        String::new()
+                    } else {
+                        format!("{literal}\n")
+                    };
+
+                    loop {
+                        let (_, line) = lines
+                            .next()
+                            .ok_or_else(|| error("unterminated multi-line string"))?;
+
+                        if let Some((last, _)) = line.split_once("'''") {
+                            input.push_str(last);
+                            break input;
+                        }
+
+                        input.push_str(line);
+                        input.push('\n');
+                    }
+                }
+            };
+            continue;
+        }
+
+        let value = answers::parse_value(value).ok_or_else(|| error("invalid value"))?;
+
+        match key {
+            "name" => case.name = value,
+            "file" => case.file = Some(value),
+            "part_one" => case.answers.part_one = Some(value),
+            "part_two" => case.answers.part_two = Some(value),
+            key => return Err(error(&format!("unknown key `{key}`"))),
+        }
+    }
+
+    for (index, case) in cases.iter_mut().enumerate() {
+        if let Some(file) = &case.file {
+            if !case.input.is_empty() {
+                return Err(Error::Parser(
+                    FILE,
+                    format!("case {} has both an `input` and a `file`", index + 1),
+                ));
+            }
+
+            case.input = read_example(file).map_err(|e| Error::IO("example", e))?;
+
+            if case.name.is_empty() {
+                case.name.clone_from(file);
+            }
+        }
+
+        if case.name.is_empty() {
+            case.name = format!("case {}", index + 1);
+        }
+    }
+
+    Ok(cases)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::io;
+
+    use super::{check_cases, parse, to_toml};
+    use crate::{day, year, PuzzleId};
+
+    const PUZZLE: PuzzleId = PuzzleId::new(year!(2023), day!(8));
+
+    fn read_example(file: &str) -> io::Result<String> {
+        match file {
+            "08-1.txt" => Ok("RL\n".into()),
+            _ => Err(io::ErrorKind::NotFound.into()),
+        }
+    }
+
+    #[test]
+    fn parses_cases() {
+        let cases = parse(
+            "# day 8\n[[case]]\nfile = \"08-1.txt\"\npart_one = 2\n\n[[case]]\nname = \"ghosts\"\ninput = '''\nLR\n\n11A = (11B, XXX)\n'''\npart_two = \"6\"\n\n[[case]]\ninput = '''abc'''\n",
+            read_example,
+        )
+        .unwrap();
+
+        assert_eq!(cases.len(), 3);
+        assert_eq!(cases[0].name, "08-1.txt");
+        assert_eq!(cases[0].input, "RL\n");
+        assert_eq!(cases[0].answers.get(1), Some("2"));
+        assert_eq!(cases[0].answers.get(2), None);
+        assert_eq!(cases[1].name, "ghosts");
+        assert_eq!(cases[1].input, "LR\n\n11A = (11B, XXX)\n");
+        assert_eq!(cases[1].answers.get(2), Some("6"));
+        assert_eq!(cases[2].name, "case 3");
+        assert_eq!(cases[2].input, "abc");
+    }
+
+    #[test]
+    fn checks_cases_with_expected_answers() {
+        let cases = parse(
+            "[[case]]\ninput = '''abc'''\npart_one = 3\n\n[[case]]\ninput = '''de'''\n",
+            read_example,
+        )
+        .unwrap();
+
+        check_cases(PUZZLE, &cases, 1, |input| Some(input.len()));
+    }
+
+    #[test]
+    #[should_panic(expected = "wrong answer for part 1 of example `case 1`")]
+    fn fails_on_wrong_answers() {
+        let cases = parse("[[case]]\ninput = '''abc'''\npart_one = 4\n", read_example).unwrap();
+        check_cases(PUZZLE, &cases, 1, |input| Some(input.len()));
+    }
+
+    #[test]
+    #[should_panic(expected = "has an expected answer for part 2")]
+    fn fails_without_expected_answers() {
+        let cases = parse("[[case]]\ninput = '''abc'''\npart_one = 3\n", read_example).unwrap();
+        check_cases(PUZZLE, &cases, 2, |input| Some(input.len()));
+    }
+
+    #[test]
+    fn round_trips_cases() {
+        let content = "[[case]]\nfile = \"08-1.txt\"\npart_one = 2\n\n[[case]]\nname = \"ghosts\"\ninput = '''\nLR\n'''\npart_two = 6\n";
+        let cases = parse(content, read_example).unwrap();
+
+        assert_eq!(to_toml(&cases), content);
+    }
+
+    #[test]
+    fn rejects_malformed_files() {
+        assert!(parse("part_one = 1", read_example).is_err());
+        assert!(parse("[[case]]\npart_three = 1", read_example).is_err());
+        assert!(parse("[[case]]\ninput = '''\nLR\n", read_example).is_err());
+        assert!(parse("[[case]]\nfile = \"missing.txt\"", read_example)
+            .unwrap_err()
+            .to_string()
+            .starts_with("could not read example: "));
+        assert!(parse(
+            "[[case]]\nfile = \"08-1.txt\"\ninput = '''x'''",
+            read_example
+        )
+        .is_err());
+    }
+}
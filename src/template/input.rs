@@ -0,0 +1,107 @@
+/// Selects the input a solution runs against: the puzzle input, an example, any file or stdin.
+use std::{
+    env,
+    fmt::Display,
+    fs,
+    io::{self, Read},
+    path::PathBuf,
+};
+
+use crate::PuzzleId;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Input {
+    /// The puzzle input, `data/<year>/inputs/<day>.txt`.
+    #[default]
+    Puzzle,
+    /// The example `data/<year>/examples/<day>.txt`, or `<day>-<n>.txt` if a number is given.
+    Example(Option<u8>),
+    File(PathBuf),
+    Stdin,
+}
+
+impl Input {
+    /// Parses the value of `--input`, where `-` stands for stdin.
+    #[must_use]
+    pub fn from_path(path: &str) -> Self {
+        match path {
+            "-" => Input::Stdin,
+            path => Input::File(path.into()),
+        }
+    }
+
+    /// Whether this is the puzzle input, the only one answers can be submitted for.
+    #[must_use]
+    pub fn is_puzzle(&self) -> bool {
+        *self == Input::Puzzle
+    }
+
+    /// The path of the file this input is read from, relative to the working directory.
+    #[must_use]
+    pub fn path(&self, puzzle: PuzzleId) -> Option<PathBuf> {
+        match self {
+            Input::Puzzle => Some(puzzle.data_path("inputs", "txt").into()),
+            Input::Example(None) => Some(puzzle.data_path("examples", "txt").into()),
+            Input::Example(Some(n)) => {
+                Some(puzzle.numbered_data_path("examples", *n, "txt").into())
+            }
+            Input::File(path) => Some(path.clone()),
+            Input::Stdin => None,
+        }
+    }
+
+    pub fn read(&self, puzzle: PuzzleId) -> io::Result<String> {
+        match self.path(puzzle) {
+            Some(path) => fs::read_to_string(env::current_dir()?.join(path)),
+            None => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+        }
+    }
+}
+
+impl Display for Input {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Input::Puzzle => f.write_str("puzzle input"),
+            Input::Example(None) => f.write_str("example"),
+            Input::Example(Some(n)) => write!(f, "example {n}"),
+            Input::File(path) => write!(f, "{}", path.display()),
+            Input::Stdin => f.write_str("stdin"),
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::Input;
+    use crate::{day, year, PuzzleId};
+
+    #[test]
+    fn resolves_paths() {
+        let puzzle = PuzzleId::new(year!(2023), day!(5));
+
+        assert_eq!(
+            Input::Puzzle.path(puzzle),
+            Some(PathBuf::from("data/2023/inputs/05.txt"))
+        );
+        assert_eq!(
+            Input::Example(None).path(puzzle),
+            Some(PathBuf::from("data/2023/examples/05.txt"))
+        );
+        assert_eq!(
+            Input::Example(Some(2)).path(puzzle),
+            Some(PathBuf::from("data/2023/examples/05-2.txt"))
+        );
+        assert_eq!(
+            Input::from_path("stress.txt").path(puzzle),
+            Some(PathBuf::from("stress.txt"))
+        );
+        assert_eq!(Input::from_path("-"), Input::Stdin);
+        assert_eq!(Input::Stdin.path(puzzle), None);
+    }
+}
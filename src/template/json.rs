@@ -0,0 +1,288 @@
+/// Minimal JSON support for the files and output formats of the template.
+///
+/// The files the template writes itself are flat objects with string, number, boolean and null
+/// values. Nested documents, like private leaderboards, can be parsed with [`parse`].
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    /// Numbers are kept in their textual form, so integers of any width survive a round-trip.
+    Number(String),
+    String(String),
+    Array(Vec<Value>),
+    Object(HashMap<String, Value>),
+}
+
+impl Value {
+    #[must_use]
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            Value::Number(n) => n.parse().ok(),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn as_object(&self) -> Option<&HashMap<String, Value>> {
+        match self {
+            Value::Object(object) => Some(object),
+            _ => None,
+        }
+    }
+
+    /// Looks up a key if this is an object.
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.as_object()?.get(key)
+    }
+}
+
+/// Encodes a string as a quoted JSON string.
+#[must_use]
+pub fn encode_string(s: &str) -> String {
+    let mut encoded = String::with_capacity(s.len() + 2);
+    encoded.push('"');
+
+    for c in s.chars() {
+        match c {
+            '"' => encoded.push_str("\\\""),
+            '\\' => encoded.push_str("\\\\"),
+            '\n' => encoded.push_str("\\n"),
+            '\r' => encoded.push_str("\\r"),
+            '\t' => encoded.push_str("\\t"),
+            c if c.is_control() => encoded.push_str(&format!("\\u{:04x}", c as u32)),
+            c => encoded.push(c),
+        }
+    }
+
+    encoded.push('"');
+    encoded
+}
+
+/// Parses a JSON document. Returns [`None`] if the input is not valid JSON.
+#[must_use]
+pub fn parse(s: &str) -> Option<Value> {
+    let mut parser = Parser {
+        chars: s.trim().chars().collect(),
+        pos: 0,
+    };
+    let value = parser.value()?;
+    parser.skip_whitespace();
+    (parser.pos == parser.chars.len()).then_some(value)
+}
+
+/// Parses a flat JSON object. Returns [`None`] if the input is not a flat object.
+#[must_use]
+pub fn parse_object(s: &str) -> Option<HashMap<String, Value>> {
+    match parse(s)? {
+        Value::Object(object)
+            if object
+                .values()
+                .all(|value| !matches!(value, Value::Array(_) | Value::Object(_))) =>
+        {
+            Some(object)
+        }
+        _ => None,
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += 1;
+        Some(c)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Option<()> {
+        self.skip_whitespace();
+        (self.next()? == expected).then_some(())
+    }
+
+    fn object(&mut self) -> Option<HashMap<String, Value>> {
+        let mut object = HashMap::new();
+        self.expect('{')?;
+
+        self.skip_whitespace();
+        if self.peek() == Some('}') {
+            self.pos += 1;
+            return Some(object);
+        }
+
+        loop {
+            self.expect('"')?;
+            let key = self.string()?;
+            self.expect(':')?;
+            let value = self.value()?;
+            object.insert(key, value);
+
+            self.skip_whitespace();
+            match self.next()? {
+                ',' => {}
+                '}' => return Some(object),
+                _ => return None,
+            }
+        }
+    }
+
+    fn value(&mut self) -> Option<Value> {
+        self.skip_whitespace();
+
+        match self.peek()? {
+            '"' => {
+                self.pos += 1;
+                self.string().map(Value::String)
+            }
+            '{' => self.object().map(Value::Object),
+            '[' => self.array().map(Value::Array),
+            'n' => self.literal("null", Value::Null),
+            't' => self.literal("true", Value::Bool(true)),
+            'f' => self.literal("false", Value::Bool(false)),
+            c if c == '-' || c.is_ascii_digit() => {
+                let start = self.pos;
+                while self
+                    .peek()
+                    .is_some_and(|c| c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E'))
+                {
+                    self.pos += 1;
+                }
+                Some(Value::Number(self.chars[start..self.pos].iter().collect()))
+            }
+            _ => None,
+        }
+    }
+
+    fn array(&mut self) -> Option<Vec<Value>> {
+        let mut array = vec![];
+        self.expect('[')?;
+
+        self.skip_whitespace();
+        if self.peek() == Some(']') {
+            self.pos += 1;
+            return Some(array);
+        }
+
+        loop {
+            array.push(self.value()?);
+
+            self.skip_whitespace();
+            match self.next()? {
+                ',' => {}
+                ']' => return Some(array),
+                _ => return None,
+            }
+        }
+    }
+
+    fn literal(&mut self, literal: &str, value: Value) -> Option<Value> {
+        for expected in literal.chars() {
+            if self.next()? != expected {
+                return None;
+            }
+        }
+        Some(value)
+    }
+
+    /// Parses the remainder of a string after its opening quote.
+    fn string(&mut self) -> Option<String> {
+        let mut value = String::new();
+
+        loop {
+            match self.next()? {
+                '"' => return Some(value),
+                '\\' => match self.next()? {
+                    '"' => value.push('"'),
+                    '\\' => value.push('\\'),
+                    '/' => value.push('/'),
+                    'n' => value.push('\n'),
+                    'r' => value.push('\r'),
+                    't' => value.push('\t'),
+                    'b' => value.push('\u{8}'),
+                    'f' => value.push('\u{c}'),
+                    'u' => {
+                        let hex: String = (0..4).filter_map(|_| self.next()).collect();
+                        value.push(char::from_u32(u32::from_str_radix(&hex, 16).ok()?)?);
+                    }
+                    _ => return None,
+                },
+                c => value.push(c),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{encode_string, parse, parse_object, Value};
+
+    #[test]
+    fn escapes_strings() {
+        assert_eq!(encode_string("42"), r#""42""#);
+        assert_eq!(encode_string("a\"b\\c\nd"), r#""a\"b\\c\nd""#);
+        assert_eq!(encode_string("\u{1b}"), r#""\u001b""#);
+    }
+
+    #[test]
+    fn parses_flat_objects() {
+        let object = parse_object(r#" {"a": "x\"yA", "b":-12, "c" :null, "d":true} "#).unwrap();
+        assert_eq!(object["a"], Value::String("x\"yA".into()));
+        assert_eq!(object["b"], Value::Number("-12".into()));
+        assert_eq!(object["c"], Value::Null);
+        assert_eq!(object["d"], Value::Bool(true));
+        assert_eq!(parse_object("{}").unwrap().len(), 0);
+    }
+
+    #[test]
+    fn round_trips_strings() {
+        let s = "tab\tquote\"backslash\\\u{1}";
+        let object = parse_object(&format!(r#"{{"s":{}}}"#, encode_string(s))).unwrap();
+        assert_eq!(object["s"].as_str(), Some(s));
+    }
+
+    #[test]
+    fn parses_nested_documents() {
+        let value = parse(r#"{"members": {"7": {"name": null, "days": [1, {"a": []}]}}}"#).unwrap();
+        let member = value.get("members").and_then(|m| m.get("7")).unwrap();
+        assert_eq!(member.get("name"), Some(&Value::Null));
+        assert_eq!(
+            member.get("days"),
+            Some(&Value::Array(vec![
+                Value::Number("1".into()),
+                Value::Object([("a".to_string(), Value::Array(vec![]))].into()),
+            ]))
+        );
+        assert!(parse(r#"[1, 2"#).is_none());
+    }
+
+    #[test]
+    fn rejects_invalid_objects() {
+        assert!(parse_object(r#"{"a":1"#).is_none());
+        assert!(parse_object(r#"{"a":[1]}"#).is_none());
+        assert!(parse_object(r#"{"a":1} x"#).is_none());
+        assert!(parse_object(r#"[]"#).is_none());
+    }
+}
@@ -0,0 +1,200 @@
+/// Remembers the outcome of every part's most recent run by `cargo all` in `data/last_run.jsonl`.
+///
+/// The file holds one JSON object per part and is rewritten after each run, replacing the outcomes
+/// of the parts that ran. It lets later runs select the days that still need work.
+use std::{fs, io, time::Duration};
+
+use crate::template::config;
+use crate::template::json::{self, Value};
+use crate::template::report::{PartReport, PartStatus};
+use crate::template::write_file;
+use crate::{Day, PuzzleId, Year};
+
+/// The file runs are recorded in, relative to the data directory.
+pub const LAST_RUN_PATH: &str = "last_run.jsonl";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    pub puzzle: PuzzleId,
+    pub part: u8,
+    pub status: PartStatus,
+    pub duration: Duration,
+}
+
+impl Outcome {
+    #[must_use]
+    pub fn from_report(report: &PartReport) -> Self {
+        Self {
+            puzzle: report.puzzle,
+            part: report.part,
+            status: report.status(),
+            duration: report.duration,
+        }
+    }
+
+    fn to_json(&self) -> String {
+        format!(
+            r#"{{"year":{},"day":{},"part":{},"status":"{}","duration_ns":{}}}"#,
+            self.puzzle.year.into_inner(),
+            self.puzzle.day.into_inner(),
+            self.part,
+            self.status,
+            self.duration.as_nanos()
+        )
+    }
+
+    fn from_json(line: &str) -> Option<Self> {
+        let object = json::parse_object(line)?;
+        let number = |key: &str| object.get(key).and_then(Value::as_u64);
+
+        let year = Year::new(u16::try_from(number("year")?).ok()?)?;
+        let day = Day::new(u8::try_from(number("day")?).ok()?)?;
+        let status = object.get("status")?.as_str()?;
+
+        Some(Self {
+            puzzle: PuzzleId::new(year, day),
+            part: u8::try_from(number("part")?).ok()?,
+            status: [
+                PartStatus::Solved,
+                PartStatus::Unsolved,
+                PartStatus::Crashed,
+                PartStatus::Overflowed,
+                PartStatus::TimedOut,
+            ]
+            .into_iter()
+            .find(|candidate| candidate.to_string() == status)?,
+            duration: Duration::from_nanos(number("duration_ns")?),
+        })
+    }
+}
+
+/// Whether a day did not solve all of the given parts in its last run. Parts that never ran count
+/// as failing.
+#[must_use]
+pub fn is_failing(outcomes: &[Outcome], puzzle: PuzzleId, parts: &[u8]) -> bool {
+    parts.iter().any(|part| {
+        find(outcomes, puzzle, *part).is_none_or(|outcome| outcome.status != PartStatus::Solved)
+    })
+}
+
+/// Whether the given parts of a day took longer than `threshold` in total in their last run.
+#[must_use]
+pub fn is_slow(outcomes: &[Outcome], puzzle: PuzzleId, parts: &[u8], threshold: Duration) -> bool {
+    let total: Duration = parts
+        .iter()
+        .filter_map(|part| find(outcomes, puzzle, *part))
+        .map(|outcome| outcome.duration)
+        .sum();
+
+    total > threshold
+}
+
+fn find(outcomes: &[Outcome], puzzle: PuzzleId, part: u8) -> Option<&Outcome> {
+    outcomes
+        .iter()
+        .find(|outcome| outcome.puzzle == puzzle && outcome.part == part)
+}
+
+/// Reads the last run outcomes. A missing file yields no outcomes.
+pub fn load() -> io::Result<Vec<Outcome>> {
+    match fs::read_to_string(config::get().data_path(LAST_RUN_PATH)) {
+        Ok(content) => Ok(content.lines().filter_map(Outcome::from_json).collect()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(vec![]),
+        Err(e) => Err(e),
+    }
+}
+
+/// Replaces the outcomes of the parts in `reports` and keeps those of all other parts.
+pub fn record(reports: &[PartReport]) -> io::Result<()> {
+    let outcomes = merge(load()?, reports);
+    let content: String = outcomes
+        .iter()
+        .map(|outcome| outcome.to_json() + "\n")
+        .collect();
+
+    write_file(config::get().data_path(LAST_RUN_PATH), content)
+}
+
+fn merge(mut outcomes: Vec<Outcome>, reports: &[PartReport]) -> Vec<Outcome> {
+    outcomes.retain(|outcome| {
+        !reports
+            .iter()
+            .any(|report| report.puzzle == outcome.puzzle && report.part == outcome.part)
+    });
+    outcomes.extend(reports.iter().map(Outcome::from_report));
+    outcomes.sort_by_key(|outcome| (outcome.puzzle, outcome.part));
+    outcomes
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{is_failing, is_slow, merge, Outcome};
+    use crate::template::report::{PartReport, PartStatus};
+    use crate::template::solution::Error;
+    use crate::{day, year, Day, PuzzleId};
+
+    fn outcome(day: u8, part: u8, status: PartStatus, millis: u64) -> Outcome {
+        Outcome {
+            puzzle: PuzzleId::new(year!(2023), Day::new(day).unwrap()),
+            part,
+            status,
+            duration: Duration::from_millis(millis),
+        }
+    }
+
+    #[test]
+    fn round_trips_outcomes() {
+        let outcome = outcome(7, 2, PartStatus::TimedOut, 1_500);
+        assert_eq!(Outcome::from_json(&outcome.to_json()), Some(outcome));
+    }
+
+    #[test]
+    fn merges_reports_into_outcomes() {
+        let report = PartReport::failed(
+            PuzzleId::new(year!(2023), day!(1)),
+            2,
+            Error::Overflow,
+            Duration::from_millis(3),
+        );
+        let outcomes = merge(
+            vec![
+                outcome(2, 1, PartStatus::Solved, 1),
+                outcome(1, 2, PartStatus::Solved, 1),
+                outcome(1, 1, PartStatus::Solved, 1),
+            ],
+            &[report],
+        );
+
+        assert_eq!(
+            outcomes,
+            vec![
+                outcome(1, 1, PartStatus::Solved, 1),
+                outcome(1, 2, PartStatus::Overflowed, 3),
+                outcome(2, 1, PartStatus::Solved, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn selects_failing_and_slow_days() {
+        let outcomes = vec![
+            outcome(1, 1, PartStatus::Solved, 10),
+            outcome(1, 2, PartStatus::Crashed, 200),
+        ];
+        let puzzle = PuzzleId::new(year!(2023), day!(1));
+
+        assert!(!is_failing(&outcomes, puzzle, &[1]));
+        assert!(is_failing(&outcomes, puzzle, &[1, 2]));
+        assert!(is_failing(
+            &outcomes,
+            PuzzleId::new(year!(2023), day!(2)),
+            &[1]
+        ));
+
+        let threshold = Duration::from_millis(100);
+        assert!(!is_slow(&outcomes, puzzle, &[1], threshold));
+        assert!(is_slow(&outcomes, puzzle, &[1, 2], threshold));
+    }
+}
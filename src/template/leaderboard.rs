@@ -0,0 +1,300 @@
+/// Reads private leaderboards and renders the stars of their members.
+///
+/// The JSON of a private leaderboard is available at
+/// `https://adventofcode.com/<year>/leaderboard/private/view/<id>.json`. The stars of one member
+/// can be written to the `<!--- advent_readme_stars table --->` section of the readme, in the same
+/// format the `advent-readme-stars` action uses.
+use std::{collections::BTreeMap, fs, time::Duration};
+
+use crate::template::answers::Error;
+use crate::template::config;
+use crate::template::json::{self, Value};
+use crate::template::readme_benchmarks;
+use crate::Year;
+
+pub const README_MARKER: &str = "<!--- advent_readme_stars table --->";
+
+/// Advent of Code asks to not request leaderboards more often than this.
+pub const MIN_REFRESH_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+/// The name of leaderboards in error messages.
+const FILE: &str = "leaderboard";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Member {
+    pub id: String,
+    /// Anonymous members have no name.
+    pub name: Option<String>,
+    pub stars: u64,
+    pub local_score: u64,
+    /// The unix timestamps the stars of each part were collected at, by day.
+    pub days: BTreeMap<u8, [Option<u64>; 2]>,
+}
+
+impl Member {
+    #[must_use]
+    pub fn display_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+
+    /// The time it took to collect the star of a part, counted from the unlock of the puzzle.
+    #[must_use]
+    pub fn completion_time(&self, year: Year, day: u8, part: u8) -> Option<Duration> {
+        let timestamp = self.days.get(&day)?[usize::from(part - 1)]?;
+        let unlocked = unlock_timestamp(year, day);
+        Some(Duration::from_secs(timestamp.saturating_sub(unlocked)))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Leaderboard {
+    pub year: Year,
+    /// Ordered by local score, then by stars.
+    pub members: Vec<Member>,
+}
+
+impl Leaderboard {
+    #[must_use]
+    pub fn member(&self, id: &str) -> Option<&Member> {
+        self.members.iter().find(|member| member.id == id)
+    }
+}
+
+pub fn parse(content: &str) -> Result<Leaderboard, Error> {
+    let document =
+        json::parse(content).ok_or_else(|| Error::Parser(FILE, "invalid JSON".into()))?;
+
+    let year = document
+        .get("event")
+        .and_then(Value::as_str)
+        .and_then(|event| event.parse().ok())
+        .ok_or_else(|| Error::Parser(FILE, "missing or invalid `event`".into()))?;
+
+    let members = document
+        .get("members")
+        .and_then(Value::as_object)
+        .ok_or_else(|| Error::Parser(FILE, "missing `members`".into()))?;
+
+    let mut members = members
+        .iter()
+        .map(|(id, member)| parse_member(id, member))
+        .collect::<Option<Vec<_>>>()
+        .ok_or_else(|| Error::Parser(FILE, "invalid member".into()))?;
+
+    members.sort_by(|a, b| {
+        (b.local_score, b.stars)
+            .cmp(&(a.local_score, a.stars))
+            .then_with(|| a.id.cmp(&b.id))
+    });
+
+    Ok(Leaderboard { year, members })
+}
+
+fn parse_member(id: &str, member: &Value) -> Option<Member> {
+    let number = |key: &str| member.get(key).and_then(Value::as_u64);
+    let mut days = BTreeMap::new();
+
+    for (day, parts) in member.get("completion_day_level")?.as_object()? {
+        let timestamp = |part: &str| {
+            parts
+                .get(part)
+                .and_then(|part| part.get("get_star_ts"))
+                .and_then(Value::as_u64)
+        };
+        days.insert(day.parse().ok()?, [timestamp("1"), timestamp("2")]);
+    }
+
+    Some(Member {
+        id: id.to_string(),
+        name: member.get("name").and_then(Value::as_str).map(String::from),
+        stars: number("stars")?,
+        local_score: number("local_score")?,
+        days,
+    })
+}
+
+/// Renders the stars and completion times of every member.
+#[must_use]
+pub fn render(leaderboard: &Leaderboard) -> String {
+    let mut lines = vec![];
+
+    for (rank, member) in leaderboard.members.iter().enumerate() {
+        lines.push(format!(
+            "{}. {} ({} ⭐, {} points)",
+            rank + 1,
+            member.display_name(),
+            member.stars,
+            member.local_score
+        ));
+
+        if member.days.is_empty() {
+            lines.push(String::new());
+            continue;
+        }
+
+        lines.push(format!("   {:>3}  {:<9} {}", "Day", "Part 1", "Part 2"));
+
+        for day in member.days.keys() {
+            let cell = |part: u8| {
+                member
+                    .completion_time(leaderboard.year, *day, part)
+                    .map_or_else(|| "-".into(), format_duration)
+            };
+            lines.push(
+                format!("   {day:>3}  {:<9} {:<9}", cell(1), cell(2))
+                    .trim_end()
+                    .to_string(),
+            );
+        }
+
+        lines.push(String::new());
+    }
+
+    lines.join("\n")
+}
+
+/// Formats a completion time as `hh:mm:ss`. Hours may exceed 24.
+#[must_use]
+pub fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+fn construct_table(year: Year, member: &Member) -> String {
+    let mut lines: Vec<String> = vec![
+        README_MARKER.into(),
+        format!("## {year} Results"),
+        String::new(),
+        "| Day | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: |".into(),
+    ];
+
+    for (day, parts) in &member.days {
+        let star = |index: usize| if parts[index].is_some() { "⭐" } else { " " };
+        lines.push(format!(
+            "| [Day {day}](https://adventofcode.com/{year}/day/{day}) | {} | {} |",
+            star(0),
+            star(1)
+        ));
+    }
+
+    lines.push(README_MARKER.into());
+    lines.join("\n")
+}
+
+fn update_content(s: &mut String, year: Year, member: &Member) -> Result<(), Error> {
+    let positions = readme_benchmarks::locate_table(s, README_MARKER)
+        .map_err(|_| Error::Parser("readme", "could not find the stars section".into()))?;
+    let table = construct_table(year, member);
+    s.replace_range(positions.pos_start..positions.pos_end, &table);
+    Ok(())
+}
+
+/// Writes the stars of a member to the readme.
+pub fn update_readme(year: Year, member: &Member) -> Result<(), Error> {
+    let path = &config::get().readme;
+    let content = fs::read(path).map_err(|e| Error::IO("readme", e))?;
+    let mut readme = String::from_utf8_lossy(&content).to_string();
+    update_content(&mut readme, year, member)?;
+    fs::write(path, &readme).map_err(|e| Error::IO("readme", e))
+}
+
+/// Puzzles unlock at midnight EST, which is 05:00 UTC.
+fn unlock_timestamp(year: Year, day: u8) -> u64 {
+    let days = days_from_civil(u64::from(year.into_inner()), 12, u64::from(day));
+    days * 86_400 + 5 * 3600
+}
+
+/// The number of days since 1970-01-01 of a date in December or earlier of a year after 1970.
+fn days_from_civil(year: u64, month: u64, day: u64) -> u64 {
+    let (year, month) = if month <= 2 {
+        (year - 1, month + 9)
+    } else {
+        (year, month - 3)
+    };
+    let era = year / 400;
+    let year_of_era = year % 400;
+    let day_of_year = (153 * month + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{parse, render, update_content, README_MARKER};
+    use crate::year;
+
+    const LEADERBOARD: &str = r#"{
+        "owner_id": 7,
+        "event": "2023",
+        "members": {
+            "7": {
+                "id": 7, "name": "Felix", "stars": 3, "local_score": 10, "global_score": 0,
+                "last_star_ts": 1701410000,
+                "completion_day_level": {
+                    "1": {
+                        "1": {"get_star_ts": 1701407100, "star_index": 1},
+                        "2": {"get_star_ts": 1701410000, "star_index": 2}
+                    },
+                    "2": {"1": {"get_star_ts": 1701493800, "star_index": 3}}
+                }
+            },
+            "9": {
+                "id": 9, "name": null, "stars": 0, "local_score": 0, "global_score": 0,
+                "last_star_ts": 0, "completion_day_level": {}
+            }
+        }
+    }"#;
+
+    #[test]
+    fn parses_leaderboards() {
+        let leaderboard = parse(LEADERBOARD).unwrap();
+
+        assert_eq!(leaderboard.year, year!(2023));
+        assert_eq!(leaderboard.members.len(), 2);
+
+        let member = &leaderboard.members[0];
+        assert_eq!(member.display_name(), "Felix");
+        assert_eq!(
+            member.completion_time(year!(2023), 1, 1),
+            Some(Duration::from_secs(5 * 60))
+        );
+        assert_eq!(member.completion_time(year!(2023), 2, 2), None);
+        assert_eq!(leaderboard.members[1].display_name(), "(anonymous user #9)");
+
+        assert!(parse(r#"{"event": "2023"}"#).is_err());
+    }
+
+    #[test]
+    fn renders_members() {
+        let rendered = render(&parse(LEADERBOARD).unwrap());
+
+        assert_eq!(
+            rendered,
+            "1. Felix (3 ⭐, 10 points)\n   Day  Part 1    Part 2\n     1  00:05:00  00:53:20\n     2  00:10:00  -\n\n2. (anonymous user #9) (0 ⭐, 0 points)\n"
+        );
+    }
+
+    #[test]
+    fn updates_readme_stars() {
+        let leaderboard = parse(LEADERBOARD).unwrap();
+        let mut readme = format!("# Advent\n\n{README_MARKER}\n\n## Usage");
+
+        update_content(&mut readme, leaderboard.year, &leaderboard.members[0]).unwrap();
+        update_content(&mut readme, leaderboard.year, &leaderboard.members[0]).unwrap();
+
+        assert_eq!(
+            readme,
+            format!("# Advent\n\n{README_MARKER}\n## 2023 Results\n\n| Day | Part 1 | Part 2 |\n| :---: | :---: | :---: |\n| [Day 1](https://adventofcode.com/2023/day/1) | ⭐ | ⭐ |\n| [Day 2](https://adventofcode.com/2023/day/2) | ⭐ |   |\n{README_MARKER}\n\n## Usage")
+        );
+    }
+}
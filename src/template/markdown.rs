@@ -0,0 +1,381 @@
+/// Converts puzzle descriptions to markdown, and finds their code blocks and the answers the text
+/// emphasizes for them.
+///
+/// Descriptions are read from `data/<year>/puzzles/<day>.md`. Code blocks are either fenced with
+/// backticks or kept as `<pre><code>` HTML. Example answers are the emphasized code spans of the
+/// text, e.g. `` `*142*` `` or `<code><em>142</em></code>`. The last one after a block, before the
+/// next block starts, is taken as the block's answer. Part two often reuses an example of part
+/// one; in that case, the answer emphasized in part two is attributed to the last block of part
+/// one that has an answer.
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use crate::template::answers::Answers;
+
+/// The answers of solved parts are shown as code, but these lines are no examples.
+const SOLVED_ANSWER_PREFIX: &str = "Your puzzle answer was";
+/// The heading of part two, which is only shown once part one is solved.
+const PART_TWO_MARKER: &str = "Part Two ---";
+
+static EMPHASIZED_CODE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"`\*+([^`*]+)\*+`|\*+`([^`]+)`\*+|<code><em>(.*?)</em></code>|<em><code>(.*?)</code></em>",
+    )
+    .expect("valid regex")
+});
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeBlock {
+    /// The part whose description contains the block.
+    pub part: u8,
+    pub content: String,
+    /// The answers the text emphasizes for this block.
+    pub answers: Answers,
+}
+
+impl CodeBlock {
+    /// The first line of the block, shortened to `width` characters.
+    #[must_use]
+    pub fn preview(&self, width: usize) -> String {
+        let line = self.content.lines().next().unwrap_or_default();
+
+        match line.char_indices().nth(width) {
+            Some((end, _)) => format!("{}…", &line[..end]),
+            None => line.to_string(),
+        }
+    }
+}
+
+/// Converts the `<article>` elements of a puzzle page to markdown.
+///
+/// Code blocks are fenced, inline code is wrapped in backticks and emphasized text in asterisks,
+/// so emphasized answers come out as `` `*142*` ``. Everything outside the articles is dropped.
+#[must_use]
+pub fn from_html(html: &str) -> String {
+    let mut markdown = String::new();
+    let mut in_article = false;
+    let mut in_pre = false;
+    let mut links: Vec<String> = vec![];
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let (text, tag) = match rest.find('<') {
+            Some(0) => match rest.find('>') {
+                Some(end) => {
+                    let tag = &rest[1..end];
+                    rest = &rest[end + 1..];
+                    ("", Some(tag))
+                }
+                None => (std::mem::take(&mut rest), None),
+            },
+            Some(start) => {
+                let text = &rest[..start];
+                rest = &rest[start..];
+                (text, None)
+            }
+            None => (std::mem::take(&mut rest), None),
+        };
+
+        if !in_article {
+            in_article = tag.is_some_and(|tag| tag.starts_with("article"));
+            continue;
+        }
+
+        if in_pre {
+            markdown.push_str(&decode_entities(text));
+        } else {
+            // whitespace between tags is insignificant outside code blocks.
+            markdown.push_str(&decode_entities(&text.replace('\n', " ")));
+        }
+
+        let Some(tag) = tag else {
+            continue;
+        };
+        let name = tag.split_whitespace().next().unwrap_or_default();
+
+        match name {
+            "/article" => {
+                in_article = false;
+                markdown.push_str("\n\n");
+            }
+            "h2" => markdown.push_str("\n## "),
+            "/h2" | "/p" | "/ul" => markdown.push_str("\n\n"),
+            "pre" => {
+                in_pre = true;
+                markdown.push_str("\n```\n");
+            }
+            "/pre" => {
+                in_pre = false;
+                if !markdown.ends_with('\n') {
+                    markdown.push('\n');
+                }
+                markdown.push_str("```\n\n");
+            }
+            "code" | "/code" if !in_pre => markdown.push('`'),
+            "em" | "/em" if !in_pre => markdown.push('*'),
+            "li" => markdown.push_str("\n- "),
+            "a" => {
+                let href = tag
+                    .split_once("href=\"")
+                    .and_then(|(_, rest)| rest.split_once('"'))
+                    .map(|(href, _)| href.to_string())
+                    .unwrap_or_default();
+                links.push(href);
+                markdown.push('[');
+            }
+            "/a" => {
+                let href = links.pop().unwrap_or_default();
+                markdown.push_str(&format!("]({href})"));
+            }
+            _ => {}
+        }
+    }
+
+    tidy(&markdown)
+}
+
+/// Trims lines outside of code blocks and collapses blank lines.
+fn tidy(markdown: &str) -> String {
+    let mut lines: Vec<&str> = vec![];
+    let mut in_code = false;
+
+    for line in markdown.lines() {
+        let is_fence = line.trim() == "```";
+        let line = if in_code && !is_fence {
+            line
+        } else {
+            line.trim()
+        };
+
+        if is_fence {
+            in_code = !in_code;
+        }
+
+        if line.is_empty() && !in_code && lines.last().is_none_or(|last| last.is_empty()) {
+            continue;
+        }
+
+        lines.push(line);
+    }
+
+    while lines.last().is_some_and(|last| last.is_empty()) {
+        lines.pop();
+    }
+
+    lines.join("\n") + "\n"
+}
+
+/// The title of a puzzle, e.g. `Day 1: Trebuchet?!`.
+#[must_use]
+pub fn title(markdown: &str) -> Option<String> {
+    markdown.lines().find_map(|line| {
+        let (_, rest) = line.split_once("--- Day ")?;
+        let (title, _) = rest.split_once(" ---")?;
+        Some(format!("Day {title}"))
+    })
+}
+
+/// Whether a description includes part two. It does not change afterwards.
+#[must_use]
+pub fn has_part_two(markdown: &str) -> bool {
+    markdown.contains(PART_TWO_MARKER)
+}
+
+/// A piece of a puzzle description, either a line of text or a code block.
+enum Chunk {
+    Text(String),
+    Code(String),
+    PartTwo,
+}
+
+#[must_use]
+pub fn code_blocks(markdown: &str) -> Vec<CodeBlock> {
+    let mut blocks: Vec<CodeBlock> = vec![];
+    let mut part = 1;
+    // the text since the last code block of the current part.
+    let mut text = String::new();
+    let mut part_two_has_blocks = false;
+
+    let assign_answer = |blocks: &mut Vec<CodeBlock>, part: u8, text: &str| {
+        let Some(answer) = last_emphasized(text) else {
+            return;
+        };
+
+        match blocks.last_mut() {
+            Some(block) if block.part == part => block.answers.set(part, &answer),
+            _ => {}
+        }
+    };
+
+    for chunk in chunks(markdown) {
+        match chunk {
+            Chunk::Text(line) => {
+                text.push_str(&line);
+                text.push('\n');
+            }
+            Chunk::Code(content) => {
+                assign_answer(&mut blocks, part, &text);
+                text.clear();
+
+                part_two_has_blocks |= part == 2;
+                blocks.push(CodeBlock {
+                    part,
+                    content,
+                    answers: Answers::default(),
+                });
+            }
+            Chunk::PartTwo => {
+                assign_answer(&mut blocks, part, &text);
+                text.clear();
+                part = 2;
+            }
+        }
+    }
+
+    if part == 1 || part_two_has_blocks {
+        assign_answer(&mut blocks, part, &text);
+    } else if let Some(answer) = last_emphasized(&text).filter(|_| part == 2) {
+        // part two refers to an example of part one.
+        if let Some(block) = blocks
+            .iter_mut()
+            .rev()
+            .find(|block| block.answers.part_one.is_some())
+        {
+            block.answers.set(2, &answer);
+        }
+    }
+
+    blocks
+}
+
+fn chunks(markdown: &str) -> Vec<Chunk> {
+    let mut chunks = vec![];
+    let mut lines = markdown.lines();
+
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim();
+
+        if trimmed.starts_with("```") {
+            let content: Vec<&str> = lines
+                .by_ref()
+                .take_while(|line| !line.trim().starts_with("```"))
+                .collect();
+            chunks.push(Chunk::Code(content.join("\n") + "\n"));
+        } else if let Some(rest) = trimmed.strip_prefix("<pre><code>") {
+            let mut content = String::new();
+            let mut line = rest.to_string();
+
+            loop {
+                if let Some((last, _)) = line.split_once("</code></pre>") {
+                    content.push_str(last);
+                    break;
+                }
+
+                content.push_str(&line);
+                content.push('\n');
+
+                match lines.next() {
+                    Some(next) => line = next.to_string(),
+                    None => break,
+                }
+            }
+
+            chunks.push(Chunk::Code(strip_tags(&content)));
+        } else if trimmed.contains(PART_TWO_MARKER) {
+            chunks.push(Chunk::PartTwo);
+        } else if !trimmed.starts_with(SOLVED_ANSWER_PREFIX) {
+            chunks.push(Chunk::Text(line.to_string()));
+        }
+    }
+
+    chunks
+}
+
+fn last_emphasized(text: &str) -> Option<String> {
+    EMPHASIZED_CODE
+        .captures_iter(text)
+        .last()
+        .and_then(|captures| captures.iter().skip(1).flatten().next())
+        .map(|answer| strip_tags(answer.as_str()).trim().to_string())
+}
+
+/// Removes HTML tags like `<em>` from code and decodes the entities AoC uses.
+fn strip_tags(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    decode_entities(&text)
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{code_blocks, from_html, title};
+
+    #[test]
+    fn finds_fenced_blocks_and_answers() {
+        let markdown = "\\--- Day 1: Trebuchet?! ---\n\nFor example:\n\n```\n1abc2\npqr3stu8vwx\n```\n\nIn this example, the values are `12` and `38`. Adding these together produces `*50*`.\n\nYour puzzle answer was `54081`.\n\n\\--- Part Two ---\n----------\n\nFor example:\n\n```\ntwo1nine\n```\n\nAdding these together produces `*29*`.\n";
+        let blocks = code_blocks(markdown);
+
+        assert_eq!(title(markdown).as_deref(), Some("Day 1: Trebuchet?!"));
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].part, 1);
+        assert_eq!(blocks[0].content, "1abc2\npqr3stu8vwx\n");
+        assert_eq!(blocks[0].answers.get(1), Some("50"));
+        assert_eq!(blocks[0].answers.get(2), None);
+        assert_eq!(blocks[1].part, 2);
+        assert_eq!(blocks[1].answers.get(2), Some("29"));
+
+        // before part one is solved, the description ends with its answer.
+        let blocks = code_blocks("For example:\n\n```\n467..114\n```\n\nThe sum is `*4361*`.\n");
+        assert_eq!(blocks[0].answers.get(1), Some("4361"));
+    }
+
+    #[test]
+    fn converts_puzzle_pages() {
+        let html = "<html><body><main>\n<article class=\"day-desc\"><h2>--- Day 6: Wait For It ---</h2><p>See <a href=\"/2023/about\">the <em>rules</em></a>:</p>\n<pre><code>Time:  <em>7</em>\nDist: 9 &lt; 10\n</code></pre>\n<ul>\n<li>You get <code><em>288</em></code>.</li>\n</ul>\n</article>\n<p>Your puzzle answer was <code>42</code>.</p><article class=\"day-desc\"><h2 id=\"part2\">--- Part Two ---</h2><p>Now <code><em>71503</em></code>.</p>\n</article></main></body></html>";
+        let markdown = from_html(html);
+
+        assert_eq!(
+            markdown,
+            "## --- Day 6: Wait For It ---\n\nSee [the *rules*](/2023/about):\n\n```\nTime:  7\nDist: 9 < 10\n```\n\n- You get `*288*`.\n\n## --- Part Two ---\n\nNow `*71503*`.\n"
+        );
+
+        assert_eq!(title(&markdown).as_deref(), Some("Day 6: Wait For It"));
+
+        let blocks = code_blocks(&markdown);
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].answers.get(1), Some("288"));
+        assert_eq!(blocks[0].answers.get(2), Some("71503"));
+    }
+
+    #[test]
+    fn attributes_part_two_answers_to_examples_of_part_one() {
+        let markdown = "<pre><code>Time:      7\nDistance:  <em>9</em>\n</code></pre>\n<p>Multiply to get <code><em>288</em></code>.</p>\n<pre><code>unrelated &lt;grid&gt;\n</code></pre>\n<h2>--- Part Two ---</h2>\n<p>Now there is <em>one race</em>: <code><em>71503</em></code>.</p>\n";
+        let blocks = code_blocks(markdown);
+
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].content, "Time:      7\nDistance:  9\n");
+        assert_eq!(blocks[0].answers.get(1), Some("288"));
+        assert_eq!(blocks[0].answers.get(2), Some("71503"));
+        assert_eq!(blocks[1].content, "unrelated <grid>\n");
+        assert_eq!(blocks[1].answers.get(1), None);
+    }
+}
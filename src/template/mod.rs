@@ -1,36 +1,176 @@
-use crate::Day;
-use std::{env, fs};
+use crate::PuzzleId;
+use std::{env, fmt::Display, fs, io, path::Path};
 
+pub mod answers;
 pub mod aoc_cli;
+#[cfg(feature = "client")]
+pub mod aoc_client;
+pub mod bench_history;
+pub mod cli;
 pub mod commands;
+pub mod config;
+pub mod examples;
+pub mod input;
+pub mod json;
+pub mod last_run;
+pub mod leaderboard;
+pub mod markdown;
 pub mod readme_benchmarks;
+pub mod registry;
+pub mod report;
 pub mod runner;
+pub mod scaffold_templates;
+pub mod solution;
+pub mod submissions;
+pub mod supervisor;
+pub mod throttle;
 
-pub const ANSI_ITALIC: &str = "\x1b[3m";
-pub const ANSI_BOLD: &str = "\x1b[1m";
-pub const ANSI_RESET: &str = "\x1b[0m";
+pub use solution::Solution;
+
+/// An escape code that styles terminal output. It displays as nothing if output is not
+/// [colored](config::Config::use_color).
+#[derive(Debug, Clone, Copy)]
+pub struct Ansi(&'static str);
+
+impl Display for Ansi {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if config::get().use_color() {
+            f.write_str(self.0)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+pub const ANSI_ITALIC: Ansi = Ansi("\x1b[3m");
+pub const ANSI_BOLD: Ansi = Ansi("\x1b[1m");
+pub const ANSI_RESET: Ansi = Ansi("\x1b[0m");
+pub const ANSI_RED: Ansi = Ansi("\x1b[31m");
+pub const ANSI_GREEN: Ansi = Ansi("\x1b[32m");
 
 /// Helper function that reads a text file to a string.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+pub fn read_file(folder: &str, puzzle: PuzzleId) -> String {
+    try_read_file(folder, puzzle).expect("could not open input file")
+}
+
+/// Helper function that reads a text file to a string, returning an error if it can't be read.
+pub fn try_read_file(folder: &str, puzzle: PuzzleId) -> io::Result<String> {
+    let cwd = env::current_dir()?;
+    let filepath = cwd.join(puzzle.data_path(folder, "txt"));
+    fs::read_to_string(filepath)
+}
+
+/// Helper function that reads a numbered text file to a string, e.g. the example for part two
+/// `data/2023/examples/08-2.txt`.
+#[must_use]
+pub fn read_file_part(folder: &str, puzzle: PuzzleId, part: u8) -> String {
+    let cwd = env::current_dir().expect("could not determine the working directory");
+    let filepath = cwd.join(puzzle.numbered_data_path(folder, part, "txt"));
+    fs::read_to_string(filepath).expect("could not open input file")
+}
+
+/// Helper function that writes a file, creating its parent directories if necessary.
+pub fn write_file(path: impl AsRef<Path>, content: impl AsRef<[u8]>) -> io::Result<()> {
+    let path = path.as_ref();
+    create_parent_dir(path)?;
+    fs::write(path, content)
+}
+
+/// Helper function that creates the parent directories of a file, e.g. before appending to it.
+pub fn create_parent_dir(path: impl AsRef<Path>) -> io::Result<()> {
+    match path.as_ref().parent() {
+        Some(dir) => fs::create_dir_all(dir),
+        None => Ok(()),
+    }
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Creates the constants `YEAR`, `DAY` and `PUZZLE` and sets up the input and runner for each part.
+///
+/// When no year is passed, it is read from the `AOC_YEAR` environment variable at compile time.
+/// The `SOLUTION` constant registers both parts with the dispatch table of the template binary.
+///
+/// Solutions may pass a `parse` function, e.g. `solution!(2023, 5, parse = parse_input)`. The
+/// input is then parsed once, both parts receive a reference to the parsed value and parsing is
+/// timed separately. Parts may take any type the reference derefs to, e.g. `&[T]` if `parse`
+/// returns a `Vec<T>`.
+///
+/// Implementations of the [`Solution`](crate::template::Solution) trait are registered with
+/// `solution!(impl MySolution)` instead.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
+    (@main $year:expr, $day:expr $(, $parse:expr)?) => {
+        /// The current year.
+        const YEAR: advent_of_code::Year = $year;
         /// The current day.
-        const DAY: advent_of_code::Day = advent_of_code::day!($day);
+        const DAY: advent_of_code::Day = $day;
+        /// The current puzzle.
+        const PUZZLE: advent_of_code::PuzzleId = advent_of_code::PuzzleId::new(YEAR, DAY);
+
+        /// The entry of this solution in the dispatch table.
+        pub const SOLUTION: advent_of_code::template::registry::Entry =
+            advent_of_code::template::registry::Entry {
+                puzzle: PUZZLE,
+                name: None,
+                run: |input, options| $crate::solution!(@run input, options $(, $parse)?),
+            };
 
+        $crate::solution!(@bin);
+    };
+    // parts are called through closures, so deref coercion lets them take e.g. `&[T]` for a
+    // parsed `Vec<T>`.
+    (@run $input:ident, $options:ident) => {
+        advent_of_code::template::runner::run_parts(
+            |input| part_one(input),
+            |input| part_two(input),
+            $input,
+            PUZZLE,
+            $options,
+        )
+    };
+    (@run $input:ident, $options:ident, $parse:expr) => {
+        advent_of_code::template::runner::run_parsed(
+            $parse,
+            |parsed| part_one(parsed),
+            |parsed| part_two(parsed),
+            $input,
+            PUZZLE,
+            $options,
+        )
+    };
+    (@bin) => {
         fn main() {
-            use advent_of_code::template::runner::*;
-            let input = advent_of_code::template::read_file("inputs", DAY);
-            run_part(part_one, &input, DAY, 1);
-            run_part(part_two, &input, DAY, 2);
+            let config = advent_of_code::template::cli::RunConfig::from_args();
+            advent_of_code::template::commands::solve::run(&SOLUTION, &config);
         }
     };
+    (impl $solution:ty) => {
+        /// The current puzzle.
+        const PUZZLE: advent_of_code::PuzzleId =
+            <$solution as advent_of_code::template::Solution>::PUZZLE;
+        /// The current year.
+        #[allow(dead_code)]
+        const YEAR: advent_of_code::Year = PUZZLE.year;
+        /// The current day.
+        #[allow(dead_code)]
+        const DAY: advent_of_code::Day = PUZZLE.day;
+
+        /// The entry of this solution in the dispatch table.
+        pub const SOLUTION: advent_of_code::template::registry::Entry =
+            advent_of_code::template::registry::Entry::of::<$solution>();
+
+        $crate::solution!(@bin);
+    };
+    ($day:expr, parse = $parse:expr) => {
+        $crate::solution!(@main advent_of_code::year!(), advent_of_code::day!($day), $parse);
+    };
+    ($day:expr) => {
+        $crate::solution!(@main advent_of_code::year!(), advent_of_code::day!($day));
+    };
+    ($year:expr, $day:expr, parse = $parse:expr) => {
+        $crate::solution!(@main advent_of_code::year!($year), advent_of_code::day!($day), $parse);
+    };
+    ($year:expr, $day:expr) => {
+        $crate::solution!(@main advent_of_code::year!($year), advent_of_code::day!($day));
+    };
 }
@@ -1,10 +1,10 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::{collections::HashMap, fs, io};
 
-use crate::Day;
-
-static MARKER: &str = "<!--- benchmarking table --->";
+use crate::template::config;
+use crate::template::report::BenchStats;
+use crate::{Day, PuzzleId, Year};
 
 #[derive(Debug)]
 pub enum Error {
@@ -21,23 +21,31 @@ impl From<std::io::Error> for Error {
 #[derive(Clone)]
 pub struct Timings {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
+    pub parse: Option<BenchStats>,
+    pub part_1: Option<BenchStats>,
+    pub part_2: Option<BenchStats>,
     pub total_nanos: f64,
 }
 
 pub struct TablePosition {
-    pos_start: usize,
-    pos_end: usize,
+    pub pos_start: usize,
+    pub pos_end: usize,
+}
+
+/// Each year has its own table, delimited by a year-scoped marker.
+#[must_use]
+pub fn get_marker(year: Year) -> String {
+    format!("<!--- benchmarking table {year} --->")
 }
 
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(puzzle: PuzzleId) -> String {
+    format!("./{}", puzzle.bin_path())
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+/// Finds the section between the first and the last occurrence of `marker`, including both.
+pub fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -58,73 +66,134 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+fn construct_table(prefix: &str, year: Year, timings: Vec<Timings>, total_millis: f64) -> String {
+    let marker = get_marker(year);
+    let header = format!("{prefix} {year} Benchmarks");
 
     let mut lines: Vec<String> = vec![
-        MARKER.into(),
+        marker.clone(),
         header,
         String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
+        "| Day | Parse | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: | :---:  |".into(),
     ];
 
     for timing in timings {
-        let path = get_path_for_bin(timing.day);
+        let path = get_path_for_bin(PuzzleId::new(year, timing.day));
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+            "| [Day {}]({}) | {} | {} | {} |",
             timing.day.into_inner(),
             path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            format_cell(timing.parse.as_ref()),
+            format_cell(timing.part_1.as_ref()),
+            format_cell(timing.part_2.as_ref())
         ));
     }
 
+    lines.push(String::new());
+    lines.push("Median ± standard deviation and 95th percentile, outliers excluded.".into());
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+    lines.push(marker);
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+fn format_cell(stats: Option<&BenchStats>) -> String {
+    match stats {
+        Some(stats) => format!(
+            "`{:.1?} ± {:.1?}` (p95 `{:.1?}`)",
+            stats.median, stats.stddev, stats.p95
+        ),
+        None => "`-`".into(),
+    }
+}
+
+fn update_content(
+    s: &mut String,
+    year: Year,
+    timings: Vec<Timings>,
+    total_millis: f64,
+) -> Result<(), Error> {
+    let positions = locate_table(s, &get_marker(year))?;
+    let table = construct_table("##", year, timings, total_millis);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
-    let path = "README.md";
+/// The medians recorded in the table of a year for the parse step and both parts, by day.
+pub fn recorded_medians(
+    readme: &str,
+    year: Year,
+) -> Result<HashMap<Day, [Option<String>; 3]>, Error> {
+    let positions = locate_table(readme, &get_marker(year))?;
+    let mut medians = HashMap::new();
+
+    for line in readme[positions.pos_start..positions.pos_end].lines() {
+        let Some(row) = line.strip_prefix("| [Day ") else {
+            continue;
+        };
+        let Some(day) = row.split(']').next().and_then(|day| day.parse().ok()) else {
+            continue;
+        };
+
+        let cells: Vec<&str> = row.split(" | ").skip(1).collect();
+        let median = |index: usize| {
+            let cell = cells.get(index)?.strip_prefix('`')?;
+            let (median, _) = cell.split_once(" ±")?;
+            Some(median.to_string())
+        };
+
+        medians.insert(day, [median(0), median(1), median(2)]);
+    }
+
+    Ok(medians)
+}
+
+pub fn update(year: Year, timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
+    let path = &config::get().readme;
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(&mut readme, year, timings, total_millis)?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, Timings, MARKER};
-    use crate::day;
+    use std::time::Duration;
+
+    use super::{get_marker, recorded_medians, update_content, Timings};
+    use crate::template::report::BenchStats;
+    use crate::{day, year, Year};
+
+    const YEAR: Year = year!(2023);
+
+    fn stats(millis: &[u64]) -> Option<BenchStats> {
+        let samples: Vec<Duration> = millis.iter().map(|x| Duration::from_millis(*x)).collect();
+        BenchStats::from_samples(&samples)
+    }
 
     fn get_mock_timings() -> Vec<Timings> {
         vec![
             Timings {
                 day: day!(1),
-                part_1: Some("10ms".into()),
-                part_2: Some("20ms".into()),
+                parse: None,
+                part_1: stats(&[10]),
+                part_2: stats(&[19, 20, 21]),
                 total_nanos: 3e+10,
             },
             Timings {
                 day: day!(2),
-                part_1: Some("30ms".into()),
-                part_2: Some("40ms".into()),
+                parse: stats(&[5]),
+                part_1: stats(&[30]),
+                part_2: None,
                 total_nanos: 7e+10,
             },
             Timings {
                 day: day!(4),
-                part_1: Some("40ms".into()),
-                part_2: Some("50ms".into()),
+                parse: None,
+                part_1: stats(&[40]),
+                part_2: stats(&[50]),
                 total_nanos: 9e+10,
             },
         ]
@@ -134,53 +203,77 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, YEAR, get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
-        let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        let marker = get_marker(YEAR);
+        let mut s = format!("{} {} {}", marker, marker, marker);
+        update_content(&mut s, YEAR, get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
-        let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        assert_eq!(s.contains("## Benchmarks"), true);
+        let marker = get_marker(YEAR);
+        let mut s = format!("foo\nbar\n{}{}\nbaz", marker, marker);
+        update_content(&mut s, YEAR, get_mock_timings(), 190.0).unwrap();
+        assert!(s.contains("## 2023 Benchmarks"));
     }
 
     #[test]
     fn updates_existing_benchmarks() {
-        let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
-        assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
+        let marker = get_marker(YEAR);
+        let mut s = format!("foo\nbar\n{}{}\nbaz", marker, marker);
+        update_content(&mut s, YEAR, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, YEAR, get_mock_timings(), 190.0).unwrap();
+        assert_eq!(s.matches(&marker).collect::<Vec<&str>>().len(), 2);
+        assert_eq!(
+            s.matches("## 2023 Benchmarks").collect::<Vec<&str>>().len(),
+            1
+        );
     }
 
     #[test]
     fn format_benchmarks() {
-        let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        let marker = get_marker(YEAR);
+        let mut s = format!("foo\nbar\n{}\n{}\nbaz", marker, marker);
+        update_content(&mut s, YEAR, get_mock_timings(), 190.0).unwrap();
         let expected = [
             "foo",
             "bar",
-            "<!--- benchmarking table --->",
-            "## Benchmarks",
+            "<!--- benchmarking table 2023 --->",
+            "## 2023 Benchmarks",
+            "",
+            "| Day | Parse | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/2023-01.rs) | `-` | `10.0ms ± 0.0ns` (p95 `10.0ms`) | `20.0ms ± 816.5µs` (p95 `21.0ms`) |",
+            "| [Day 2](./src/bin/2023-02.rs) | `5.0ms ± 0.0ns` (p95 `5.0ms`) | `30.0ms ± 0.0ns` (p95 `30.0ms`) | `-` |",
+            "| [Day 4](./src/bin/2023-04.rs) | `-` | `40.0ms ± 0.0ns` (p95 `40.0ms`) | `50.0ms ± 0.0ns` (p95 `50.0ms`) |",
             "",
-            "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "Median ± standard deviation and 95th percentile, outliers excluded.",
             "",
             "**Total: 190.00ms**",
-            "<!--- benchmarking table --->",
+            "<!--- benchmarking table 2023 --->",
             "baz",
         ]
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn reads_recorded_medians() {
+        let marker = get_marker(YEAR);
+        let mut s = format!("foo\n{}{}\nbaz", marker, marker);
+        update_content(&mut s, YEAR, get_mock_timings(), 190.0).unwrap();
+
+        let medians = recorded_medians(&s, YEAR).unwrap();
+        assert_eq!(medians.len(), 3);
+        assert_eq!(
+            medians[&day!(2)],
+            [Some("5.0ms".into()), Some("30.0ms".into()), None]
+        );
+        assert_eq!(medians[&day!(4)][2].as_deref(), Some("50.0ms"));
+    }
 }
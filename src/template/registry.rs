@@ -0,0 +1,36 @@
+/// Types shared between the `solution!` macro and the dispatch table of the template binary.
+///
+/// Every solution module exports an [`Entry`]. A build script collects these entries from
+/// `src/bin/<year>-<day>.rs` into a table, so `all` and `solve` can run solutions in-process.
+use crate::template::report::RunReport;
+use crate::template::runner::{self, RunOptions};
+use crate::template::Solution;
+use crate::PuzzleId;
+
+/// A solution that can be invoked directly, without spawning its binary.
+#[derive(Clone, Copy)]
+pub struct Entry {
+    pub puzzle: PuzzleId,
+    /// A human-readable name of the solution, if it has one.
+    pub name: Option<&'static str>,
+    /// Runs every part of the solution against the provided input.
+    pub run: fn(&str, &RunOptions) -> RunReport,
+}
+
+impl Entry {
+    /// Creates the entry of a [`Solution`] implementation.
+    #[must_use]
+    pub const fn of<S: Solution>() -> Self {
+        Self {
+            puzzle: S::PUZZLE,
+            name: S::NAME,
+            run: runner::run_solution::<S>,
+        }
+    }
+}
+
+/// Looks up the solution for a puzzle in a dispatch table.
+#[must_use]
+pub fn find(solutions: &[Entry], puzzle: PuzzleId) -> Option<&Entry> {
+    solutions.iter().find(|solution| solution.puzzle == puzzle)
+}
@@ -0,0 +1,599 @@
+/// Structured results of solution runs and the formatter that prints them.
+use std::error;
+use std::fmt::Display;
+use std::io::{stdout, Write};
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::template::solution::Error;
+use crate::template::{json, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::PuzzleId;
+
+/// Samples further than this many scaled MADs from the median are rejected as outliers.
+const OUTLIER_THRESHOLD: f64 = 3.0;
+
+/// Scales the MAD to be a consistent estimator of the standard deviation for normal distributions.
+const MAD_SCALE: f64 = 1.4826;
+
+/// Statistics collected while benching a solution part.
+///
+/// Samples that deviate from the median by more than three scaled median absolute deviations (MAD)
+/// are rejected as outliers before any other statistic is computed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchStats {
+    pub mean: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+    pub min: Duration,
+    pub max: Duration,
+    pub stddev: Duration,
+    /// The number of samples that were rejected as outliers.
+    pub outliers: usize,
+}
+
+impl BenchStats {
+    /// Computes statistics from the recorded samples. Returns [`None`] if there are no samples.
+    #[must_use]
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        let mut nanos: Vec<u128> = samples.iter().map(Duration::as_nanos).collect();
+        nanos.sort_unstable();
+
+        let median = percentile(&nanos, 50.0)?;
+
+        let mut deviations: Vec<u128> = nanos.iter().map(|x| x.abs_diff(median)).collect();
+        deviations.sort_unstable();
+
+        #[allow(clippy::cast_precision_loss)]
+        let mad = percentile(&deviations, 50.0)? as f64 * MAD_SCALE;
+
+        let kept: Vec<u128> = if mad > 0.0 {
+            #[allow(clippy::cast_precision_loss)]
+            nanos
+                .iter()
+                .copied()
+                .filter(|x| x.abs_diff(median) as f64 <= OUTLIER_THRESHOLD * mad)
+                .collect()
+        } else {
+            nanos.clone()
+        };
+
+        let count = kept.len() as u128;
+        let mean = kept.iter().sum::<u128>() / count;
+
+        #[allow(clippy::cast_precision_loss)]
+        let variance = kept
+            .iter()
+            .map(|x| (x.abs_diff(mean) as f64).powi(2))
+            .sum::<f64>()
+            / count as f64;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let stddev = variance.sqrt().round() as u128;
+
+        Some(Self {
+            mean: from_nanos(mean),
+            median: from_nanos(percentile(&kept, 50.0)?),
+            p95: from_nanos(percentile(&kept, 95.0)?),
+            min: from_nanos(*kept.first()?),
+            max: from_nanos(*kept.last()?),
+            stddev: from_nanos(stddev),
+            outliers: nanos.len() - kept.len(),
+        })
+    }
+}
+
+/// Returns the nearest-rank percentile of sorted values.
+fn percentile(sorted: &[u128], p: f64) -> Option<u128> {
+    if sorted.is_empty() {
+        return None;
+    }
+
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted.get(rank.clamp(1, sorted.len()) - 1).copied()
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn from_nanos(nanos: u128) -> Duration {
+    Duration::from_nanos(nanos as u64)
+}
+
+/// The outcome of running a single solution part.
+#[derive(Debug, Clone)]
+pub struct PartReport {
+    pub puzzle: PuzzleId,
+    pub part: u8,
+    pub answer: Option<String>,
+    /// Why the part did not produce an answer.
+    pub error: Option<Error>,
+    /// The median duration if the part was benched, the duration of the single run otherwise.
+    pub duration: Duration,
+    pub samples: u128,
+    /// Present if the part was benched.
+    pub stats: Option<BenchStats>,
+}
+
+impl PartReport {
+    /// Creates the report of a part that did not produce an answer.
+    #[must_use]
+    pub fn failed(puzzle: PuzzleId, part: u8, error: Error, duration: Duration) -> Self {
+        Self {
+            puzzle,
+            part,
+            answer: None,
+            error: Some(error),
+            duration,
+            samples: 1,
+            stats: None,
+        }
+    }
+
+    /// Whether the part was benched rather than executed once.
+    #[must_use]
+    pub fn is_benched(&self) -> bool {
+        self.stats.is_some()
+    }
+
+    #[must_use]
+    pub fn status(&self) -> PartStatus {
+        match (&self.answer, &self.error) {
+            (Some(_), _) => PartStatus::Solved,
+            (None, None | Some(Error::Unsolved)) => PartStatus::Unsolved,
+            (None, Some(Error::Failed(_) | Error::Panicked(_))) => PartStatus::Crashed,
+            (None, Some(Error::Overflow)) => PartStatus::Overflowed,
+            (None, Some(Error::TimedOut(_))) => PartStatus::TimedOut,
+        }
+    }
+
+    /// Serializes the report as a single-line JSON object.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        let answer = self
+            .answer
+            .as_deref()
+            .map_or_else(|| "null".into(), json::encode_string);
+
+        let error = match &self.error {
+            None | Some(Error::Unsolved) => "null".into(),
+            Some(error) => json::encode_string(&error.to_string()),
+        };
+
+        format!(
+            r#"{{"year":{},"day":{},"part":{},"status":"{}","answer":{},"error":{},"duration_ns":{},"samples":{},"stats":{}}}"#,
+            self.puzzle.year.into_inner(),
+            self.puzzle.day.into_inner(),
+            self.part,
+            self.status(),
+            answer,
+            error,
+            self.duration.as_nanos(),
+            self.samples,
+            stats_to_json(self.stats.as_ref())
+        )
+    }
+}
+
+/// The time spent turning the raw input into the shared input of both parts.
+#[derive(Debug, Clone)]
+pub struct ParseReport {
+    pub puzzle: PuzzleId,
+    /// The median duration if parsing was benched, the duration of the single run otherwise.
+    pub duration: Duration,
+    pub samples: u128,
+    /// Present if parsing was benched.
+    pub stats: Option<BenchStats>,
+}
+
+impl ParseReport {
+    /// Serializes the report as a single-line JSON object. It is told apart from parts by its `step`.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        format!(
+            r#"{{"year":{},"day":{},"step":"parse","duration_ns":{},"samples":{},"stats":{}}}"#,
+            self.puzzle.year.into_inner(),
+            self.puzzle.day.into_inner(),
+            self.duration.as_nanos(),
+            self.samples,
+            stats_to_json(self.stats.as_ref())
+        )
+    }
+}
+
+/// Everything a single run of a solution produced.
+#[derive(Debug, Clone, Default)]
+pub struct RunReport {
+    /// Present if the solution has a separate `parse` step.
+    pub parse: Option<ParseReport>,
+    pub parts: Vec<PartReport>,
+}
+
+impl RunReport {
+    /// The time spent on parsing and both parts.
+    #[must_use]
+    pub fn total_duration(&self) -> Duration {
+        self.parse
+            .iter()
+            .map(|parse| parse.duration)
+            .sum::<Duration>()
+            + self
+                .parts
+                .iter()
+                .map(|part| part.duration)
+                .sum::<Duration>()
+    }
+
+    fn to_json_items(&self) -> impl Iterator<Item = String> + '_ {
+        self.parse
+            .iter()
+            .map(ParseReport::to_json)
+            .chain(self.parts.iter().map(PartReport::to_json))
+    }
+}
+
+fn stats_to_json(stats: Option<&BenchStats>) -> String {
+    stats.map_or_else(
+        || "null".into(),
+        |stats| {
+            format!(
+                r#"{{"mean_ns":{},"median_ns":{},"p95_ns":{},"min_ns":{},"max_ns":{},"stddev_ns":{},"outliers":{}}}"#,
+                stats.mean.as_nanos(),
+                stats.median.as_nanos(),
+                stats.p95.as_nanos(),
+                stats.min.as_nanos(),
+                stats.max.as_nanos(),
+                stats.stddev.as_nanos(),
+                stats.outliers
+            )
+        },
+    )
+}
+
+/// Whether a part produced an answer, and why not.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PartStatus {
+    Solved,
+    Unsolved,
+    /// The part failed with an error or panicked.
+    Crashed,
+    Overflowed,
+    TimedOut,
+}
+
+impl Display for PartStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PartStatus::Solved => f.write_str("solved"),
+            PartStatus::Unsolved => f.write_str("unsolved"),
+            PartStatus::Crashed => f.write_str("crashed"),
+            PartStatus::Overflowed => f.write_str("overflowed"),
+            PartStatus::TimedOut => f.write_str("timed_out"),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// How results are written to stdout.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable, decorated text.
+    #[default]
+    Text,
+    /// A single JSON array with every report, printed once all parts ran.
+    Json,
+    /// One JSON object per line, printed as soon as a part finished.
+    Jsonl,
+}
+
+impl FromStr for OutputFormat {
+    type Err = OutputFormatFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            "jsonl" => Ok(Self::Jsonl),
+            _ => Err(OutputFormatFromStrError),
+        }
+    }
+}
+
+/// An error which can be returned when parsing an [`OutputFormat`].
+#[derive(Debug)]
+pub struct OutputFormatFromStrError;
+
+impl error::Error for OutputFormatFromStrError {}
+
+impl Display for OutputFormatFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting one of `text`, `json` or `jsonl`")
+    }
+}
+
+/// Prints the reports of a finished run as a JSON array. This is a no-op for other formats,
+/// which print each part as soon as it finished.
+pub fn print_collected(reports: &[RunReport], format: OutputFormat) {
+    if format == OutputFormat::Json {
+        let items: Vec<String> = reports.iter().flat_map(RunReport::to_json_items).collect();
+        println!("[{}]", items.join(","));
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[must_use]
+pub fn format_duration(duration: &Duration, samples: u128) -> String {
+    if samples == 1 {
+        format!(" ({duration:.1?})")
+    } else {
+        format!(" ({duration:.1?} @ {samples} samples)")
+    }
+}
+
+/// Formats the timing of a benched part, e.g. ` (1.9µs ± 0.2µs @ 100 samples; min 1.2µs, p95 2.5µs, max 3.0µs)`.
+#[must_use]
+pub fn format_stats(stats: &BenchStats, samples: u128) -> String {
+    let outliers = match stats.outliers {
+        0 => String::new(),
+        1 => ", 1 outlier".into(),
+        n => format!(", {n} outliers"),
+    };
+
+    format!(
+        " ({:.1?} ± {:.1?} @ {samples} samples; min {:.1?}, p95 {:.1?}, max {:.1?}{outliers})",
+        stats.median, stats.stddev, stats.min, stats.p95, stats.max
+    )
+}
+
+/// Prints the answer of a part before it is benched. The line is overwritten by [`print_report`].
+pub fn print_intermediate(part: u8, answer: Option<&str>, is_timed: bool) {
+    print_result(answer, &format!("Part {part}"), "");
+
+    if is_timed {
+        print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+    }
+
+    let _ = stdout().flush();
+}
+
+/// Prints the parse step before it is benched. The line is overwritten by [`print_parse_report`].
+pub fn print_parse_intermediate(is_timed: bool) {
+    if is_timed {
+        print!("Parse: > {ANSI_ITALIC}benching{ANSI_RESET}");
+        let _ = stdout().flush();
+    }
+}
+
+/// Prints the final output for the parse step in the requested format.
+pub fn print_parse_report(report: &ParseReport, format: OutputFormat) {
+    match format {
+        OutputFormat::Text => {
+            let duration_str = match &report.stats {
+                Some(stats) => format_stats(stats, report.samples),
+                None => format_duration(&report.duration, report.samples),
+            };
+            print!("\r");
+            println!("Parse:{duration_str}");
+        }
+        OutputFormat::Jsonl => println!("{}", report.to_json()),
+        OutputFormat::Json => {}
+    }
+}
+
+/// Prints the final output for a part in the requested format.
+pub fn print_report(report: &PartReport, format: OutputFormat) {
+    match format {
+        OutputFormat::Text => print_text(report),
+        OutputFormat::Jsonl => println!("{}", report.to_json()),
+        // JSON arrays are printed by `print_collected` once all parts ran.
+        OutputFormat::Json => {}
+    }
+}
+
+fn print_text(report: &PartReport) {
+    let duration_str = match &report.stats {
+        Some(stats) => format_stats(stats, report.samples),
+        None => format_duration(&report.duration, report.samples),
+    };
+
+    let part = format!("Part {}", report.part);
+
+    match &report.error {
+        Some(error @ Error::TimedOut(_)) => {
+            print!("\r");
+            println!("{part}: ✖ {ANSI_BOLD}{error}{ANSI_RESET}");
+        }
+        Some(Error::Failed(message) | Error::Panicked(message)) => {
+            print!("\r");
+            println!(
+                "{part}: ✖ {ANSI_BOLD}{}{ANSI_RESET}: {message}{duration_str}",
+                report.status()
+            );
+        }
+        Some(error @ Error::Overflow) => {
+            print!("\r");
+            println!(
+                "{part}: ✖ {ANSI_BOLD}{}{ANSI_RESET}: {error}{duration_str}",
+                report.status()
+            );
+        }
+        Some(Error::Unsolved) | None => {
+            print_result(report.answer.as_deref(), &part, &duration_str);
+        }
+    }
+}
+
+fn print_result(result: Option<&str>, part: &str, duration_str: &str) {
+    let is_intermediate_result = duration_str.is_empty();
+
+    match result {
+        Some(result) => {
+            if result.contains('\n') {
+                let str = format!("{part}: ▼ {duration_str}");
+                if is_intermediate_result {
+                    print!("{str}");
+                } else {
+                    print!("\r");
+                    println!("{str}");
+                    println!("{result}");
+                }
+            } else {
+                let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
+                if is_intermediate_result {
+                    print!("{str}");
+                } else {
+                    print!("\r");
+                    println!("{str}");
+                }
+            }
+        }
+        None => {
+            if is_intermediate_result {
+                print!("{part}: ✖");
+            } else {
+                print!("\r");
+                println!("{part}: ✖             ");
+            }
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{
+        format_duration, format_stats, BenchStats, ParseReport, PartReport, PartStatus, RunReport,
+    };
+    use crate::template::solution::Error;
+    use crate::{day, year, PuzzleId};
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|x| Duration::from_nanos(*x)).collect()
+    }
+
+    #[test]
+    fn computes_bench_stats() {
+        let stats = BenchStats::from_samples(&nanos(&[30, 10, 20, 40])).unwrap();
+        assert_eq!(stats.mean, Duration::from_nanos(25));
+        assert_eq!(stats.median, Duration::from_nanos(20));
+        assert_eq!(stats.p95, Duration::from_nanos(40));
+        assert_eq!(stats.min, Duration::from_nanos(10));
+        assert_eq!(stats.max, Duration::from_nanos(40));
+        assert_eq!(stats.stddev, Duration::from_nanos(11));
+        assert_eq!(stats.outliers, 0);
+        assert!(BenchStats::from_samples(&[]).is_none());
+    }
+
+    #[test]
+    fn rejects_outliers() {
+        let stats = BenchStats::from_samples(&nanos(&[100, 102, 98, 101, 99, 100, 5000])).unwrap();
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.max, Duration::from_nanos(102));
+        assert_eq!(stats.mean, Duration::from_nanos(100));
+    }
+
+    #[test]
+    fn keeps_identical_samples() {
+        let stats = BenchStats::from_samples(&nanos(&[7, 7, 7])).unwrap();
+        assert_eq!(stats.outliers, 0);
+        assert_eq!(stats.stddev, Duration::ZERO);
+    }
+
+    #[test]
+    fn formats_durations() {
+        let duration = Duration::from_micros(1500);
+        assert_eq!(format_duration(&duration, 1), " (1.5ms)");
+        assert_eq!(format_duration(&duration, 20), " (1.5ms @ 20 samples)");
+    }
+
+    #[test]
+    fn formats_stats() {
+        let stats = BenchStats::from_samples(&nanos(&[1000, 1200, 1400, 90_000])).unwrap();
+        assert_eq!(
+            format_stats(&stats, 4),
+            " (1.2µs ± 163.0ns @ 4 samples; min 1.0µs, p95 1.4µs, max 1.4µs, 1 outlier)"
+        );
+    }
+
+    #[test]
+    fn serializes_reports() {
+        let mut report = PartReport {
+            puzzle: PuzzleId::new(year!(2023), day!(5)),
+            part: 2,
+            answer: Some("46".into()),
+            error: None,
+            duration: Duration::from_nanos(1200),
+            samples: 1,
+            stats: None,
+        };
+        assert_eq!(
+            report.to_json(),
+            r#"{"year":2023,"day":5,"part":2,"status":"solved","answer":"46","error":null,"duration_ns":1200,"samples":1,"stats":null}"#
+        );
+
+        report.answer = None;
+        report.samples = 10;
+        report.stats = BenchStats::from_samples(&nanos(&[1000, 1200, 1500]));
+        assert_eq!(
+            report.to_json(),
+            r#"{"year":2023,"day":5,"part":2,"status":"unsolved","answer":null,"error":null,"duration_ns":1200,"samples":10,"stats":{"mean_ns":1233,"median_ns":1200,"p95_ns":1500,"min_ns":1000,"max_ns":1500,"stddev_ns":205,"outliers":0}}"#
+        );
+    }
+
+    #[test]
+    fn serializes_parse_reports() {
+        let parse = ParseReport {
+            puzzle: PuzzleId::new(year!(2023), day!(5)),
+            duration: Duration::from_nanos(800),
+            samples: 1,
+            stats: None,
+        };
+        assert_eq!(
+            parse.to_json(),
+            r#"{"year":2023,"day":5,"step":"parse","duration_ns":800,"samples":1,"stats":null}"#
+        );
+
+        let run = RunReport {
+            parse: Some(parse),
+            parts: vec![PartReport {
+                puzzle: PuzzleId::new(year!(2023), day!(5)),
+                part: 1,
+                answer: None,
+                error: None,
+                duration: Duration::from_nanos(200),
+                samples: 1,
+                stats: None,
+            }],
+        };
+        assert_eq!(run.total_duration(), Duration::from_nanos(1000));
+        assert_eq!(run.to_json_items().count(), 2);
+    }
+
+    #[test]
+    fn reports_failures() {
+        let mut report = PartReport {
+            puzzle: PuzzleId::new(year!(2023), day!(5)),
+            part: 1,
+            answer: None,
+            error: Some(Error::Overflow),
+            duration: Duration::from_nanos(10),
+            samples: 1,
+            stats: None,
+        };
+        assert_eq!(report.status(), PartStatus::Overflowed);
+
+        report.error = Some(Error::Failed("no \"start\"".into()));
+        assert_eq!(report.status(), PartStatus::Crashed);
+        assert!(report
+            .to_json()
+            .contains(r#""status":"crashed","answer":null,"error":"no \"start\"""#));
+
+        report.error = Some(Error::Unsolved);
+        assert_eq!(report.status(), PartStatus::Unsolved);
+    }
+}
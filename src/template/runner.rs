@@ -1,63 +1,345 @@
 /// Encapsulates code that interacts with solution functions.
-use crate::template::{aoc_cli, ANSI_ITALIC, ANSI_RESET};
-use crate::Day;
-use std::fmt::Display;
-use std::io::{stdout, Write};
-use std::process::Output;
+#[cfg(not(feature = "client"))]
+use crate::template::aoc_cli;
+use crate::template::report::{self, BenchStats, OutputFormat, ParseReport, PartReport, RunReport};
+use crate::template::solution::{Error, IntoAnswer};
+use crate::template::submissions::{self, Check, Submission, Verdict};
+use crate::template::{answers, config, throttle, Solution};
+use crate::PuzzleId;
+use std::any::Any;
+use std::cell::RefCell;
+use std::cmp;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::Sender;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
 
-use super::ANSI_BOLD;
+/// Flags that control how solution parts are executed.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RunOptions {
+    /// Bench each part instead of executing it once.
+    pub time: bool,
+    /// Submit the answer of the given part to the puzzle server.
+    pub submit: Option<u8>,
+    /// How results are written to stdout.
+    pub format: OutputFormat,
+    /// Suppress all output, e.g. when results are only compared against stored answers.
+    pub quiet: bool,
+    /// Abandon a step if its first execution takes longer than this. See [`supervisor`](crate::template::supervisor).
+    pub timeout: Option<Duration>,
+    /// Only run the given part.
+    pub part: Option<u8>,
+}
+
+impl RunOptions {
+    /// Whether a part is selected by [`RunOptions::part`].
+    #[must_use]
+    pub fn runs_part(&self, part: u8) -> bool {
+        self.part.is_none_or(|selected| selected == part)
+    }
+}
+
+/// Parses a timeout given in (fractional) seconds.
+pub fn parse_timeout(seconds: &str) -> Result<Duration, String> {
+    seconds
+        .parse::<f64>()
+        .ok()
+        .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+        .filter(|timeout| !timeout.is_zero())
+        .ok_or_else(|| {
+            format!("invalid timeout `{seconds}`, expected a positive number of seconds")
+        })
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A step of a solution run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    Parse,
+    Part(u8),
+}
+
+/// The progress of a solution run.
+///
+/// Events are printed as they happen, unless the run happens on a worker thread of the
+/// [`supervisor`](crate::template::supervisor), which prints them instead.
+#[derive(Debug, Clone)]
+pub enum Event {
+    Started(Step),
+    /// The first execution of a step finished. Only parts carry an answer.
+    Answered(Step, Option<String>),
+    Parsed(ParseReport),
+    Finished(PartReport),
+}
+
+thread_local! {
+    static EVENTS: RefCell<Option<Sender<Event>>> = const { RefCell::new(None) };
+}
+
+/// Sends the events of all runs on the current thread to `sender` instead of printing them.
+pub fn forward_events(sender: Sender<Event>) {
+    EVENTS.with(|events| *events.borrow_mut() = Some(sender));
+}
+
+/// Prints or forwards an event. Returns `false` if forwarded events are no longer received,
+/// i.e. the run was abandoned.
+fn emit(event: Event, options: &RunOptions) -> bool {
+    EVENTS.with(|events| match &*events.borrow() {
+        Some(sender) => sender.send(event).is_ok(),
+        None => {
+            print_event(&event, options);
+            true
+        }
+    })
+}
+
+pub fn print_event(event: &Event, options: &RunOptions) {
+    if options.quiet {
+        return;
+    }
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
-    let part_str = format!("Part {part}");
+    let is_text = options.format == OutputFormat::Text;
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    match event {
+        Event::Answered(Step::Parse, _) if is_text => {
+            report::print_parse_intermediate(options.time);
+        }
+        Event::Answered(Step::Part(part), answer) if is_text => {
+            report::print_intermediate(*part, answer.as_deref(), options.time);
+        }
+        Event::Parsed(report) => report::print_parse_report(report, options.format),
+        Event::Finished(report) => report::print_report(report, options.format),
+        Event::Started(_) | Event::Answered(..) => {}
+    }
+}
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+/* -------------------------------------------------------------------------- */
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+/// Runs a [`Solution`] implementation. The input is parsed once for both parts, or as part of
+/// each part if parsing is not [separate](Solution::SEPARATE_PARSE).
+pub fn run_solution<S: Solution>(input: &str, options: &RunOptions) -> RunReport {
+    if S::SEPARATE_PARSE {
+        run_parsed(
+            S::parse,
+            S::part_one,
+            S::part_two,
+            input,
+            S::PUZZLE,
+            options,
+        )
+    } else {
+        run_parts(
+            |input| S::part_one(&S::parse(input)),
+            |input| S::part_two(&S::parse(input)),
+            input,
+            S::PUZZLE,
+            options,
+        )
     }
 }
 
-/// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
-///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+/// Runs both parts of a solution against the raw input.
+pub fn run_parts<R1: IntoAnswer, R2: IntoAnswer>(
+    part_one: impl Fn(&str) -> R1,
+    part_two: impl Fn(&str) -> R2,
+    input: &str,
+    puzzle: PuzzleId,
+    options: &RunOptions,
+) -> RunReport {
+    RunReport {
+        parse: None,
+        parts: run_selected(options, |part| match part {
+            1 => run_part(&part_one, input, puzzle, 1, options),
+            _ => run_part(&part_two, input, puzzle, 2, options),
+        }),
+    }
+}
+
+/// Parses the input once and runs both parts of a solution against the parsed input.
+/// Parsing is timed separately from the parts. If parsing panics, both parts are reported as crashed.
+pub fn run_parsed<'a, P, R1: IntoAnswer, R2: IntoAnswer>(
+    parse: impl Fn(&'a str) -> P,
+    part_one: impl Fn(&P) -> R1,
+    part_two: impl Fn(&P) -> R2,
+    input: &'a str,
+    puzzle: PuzzleId,
+    options: &RunOptions,
+) -> RunReport {
+    match run_parse(parse, input, puzzle, options) {
+        Ok((parsed, parse_report)) => RunReport {
+            parse: Some(parse_report),
+            parts: run_selected(options, |part| match part {
+                1 => run_part(&part_one, &parsed, puzzle, 1, options),
+                _ => run_part(&part_two, &parsed, puzzle, 2, options),
+            }),
+        },
+        Err(error) => crashed(puzzle, &error, options),
+    }
+}
+
+/// Reports the selected parts as failed with `error`, e.g. because parsing panicked.
+fn crashed(puzzle: PuzzleId, error: &Error, options: &RunOptions) -> RunReport {
+    RunReport {
+        parse: None,
+        parts: run_selected(options, |part| {
+            let report = PartReport::failed(puzzle, part, error.clone(), Duration::ZERO);
+            emit(Event::Finished(report.clone()), options);
+            report
+        }),
+    }
+}
+
+fn run_selected(options: &RunOptions, run: impl FnMut(u8) -> PartReport) -> Vec<PartReport> {
+    [1, 2]
+        .into_iter()
+        .filter(|part| options.runs_part(*part))
+        .map(run)
+        .collect()
+}
+
+fn run_parse<'a, P>(
+    parse: impl Fn(&'a str) -> P,
+    input: &'a str,
+    puzzle: PuzzleId,
+    options: &RunOptions,
+) -> Result<(P, ParseReport), Error> {
+    emit(Event::Started(Step::Parse), options);
+
+    let measured = panic::catch_unwind(AssertUnwindSafe(|| {
+        run_timed(&parse, input, options.time, |_| {
+            emit(Event::Answered(Step::Parse, None), options);
+        })
+    }));
+
+    let (parsed, duration, samples, stats) = measured
+        .map_err(|payload| Error::Panicked(format!("parse: {}", panic_message(&*payload))))?;
+
+    let report = ParseReport {
+        puzzle,
+        duration,
+        samples,
+        stats,
+    };
+
+    emit(Event::Parsed(report.clone()), options);
+
+    Ok((parsed, report))
+}
+
+pub fn run_part<I: Clone, R: IntoAnswer>(
+    func: impl Fn(I) -> R,
+    input: I,
+    puzzle: PuzzleId,
+    part: u8,
+    options: &RunOptions,
+) -> PartReport {
+    emit(Event::Started(Step::Part(part)), options);
+
+    let report = measure_part(func, input, puzzle, part, options.time, |answer| {
+        emit(
+            Event::Answered(Step::Part(part), answer.map(String::from)),
+            options,
+        );
+    });
+
+    // answers of abandoned runs are discarded by the supervisor, so they are not submitted either.
+    let is_received = emit(Event::Finished(report.clone()), options);
+
+    if let (true, Some(answer)) = (is_received, &report.answer) {
+        submit_result(answer, puzzle, part, options);
+    }
+
+    report
+}
+
+/// Runs a solution part without printing anything. `on_answer` is called once the first execution finished.
+/// Panics are caught and reported as [`Error::Panicked`].
+pub fn measure_part<I: Clone, R: IntoAnswer>(
+    func: impl Fn(I) -> R,
+    input: I,
+    puzzle: PuzzleId,
+    part: u8,
+    is_timed: bool,
+    on_answer: impl Fn(Option<&str>),
+) -> PartReport {
+    let timer = Instant::now();
+
+    let measured = panic::catch_unwind(AssertUnwindSafe(|| {
+        run_timed(
+            |input| func(input).into_answer().map(|answer| answer.to_string()),
+            input,
+            is_timed,
+            |result| on_answer(result.as_deref().ok()),
+        )
+    }));
+
+    let (result, duration, samples, stats) = measured.unwrap_or_else(|payload| {
+        let error = Error::Panicked(panic_message(&*payload));
+        (Err(error), timer.elapsed(), 1, None)
+    });
+
+    match result {
+        Ok(answer) => PartReport {
+            puzzle,
+            part,
+            answer: Some(answer),
+            error: None,
+            duration,
+            samples,
+            stats,
+        },
+        Err(error) => PartReport {
+            samples,
+            stats,
+            ..PartReport::failed(puzzle, part, error, duration)
+        },
+    }
+}
+
+/// Extracts the message of a caught panic.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".into()
+    }
+}
+
+/// Run a solution part. The behavior differs depending on whether the part is timed:
+///  1. if not, the function is executed once.
+///  2. if it is, the function is warmed up and benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
+    is_timed: bool,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, Duration, u128, Option<BenchStats>) {
     let timer = Instant::now();
     let result = func(input.clone());
     let base_time = timer.elapsed();
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    if is_timed {
+        let (samples, stats) = bench(func, input, &base_time);
+        (result, stats.median, samples, Some(stats))
     } else {
-        (base_time, 1)
-    };
-
-    (result, run.0, run.1)
+        (result, base_time, 1, None)
+    }
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (u128, BenchStats) {
+    let settings = config::get().bench;
+    let bench_iterations = (settings.budget.as_nanos() / cmp::max(base_time.as_nanos(), 10))
+        .clamp(settings.min_iterations, settings.max_iterations);
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
-
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
+    // warm up caches and the branch predictor before samples are recorded.
+    let warmup_iterations = (bench_iterations / 10).clamp(1, 100);
+    for _ in 0..warmup_iterations {
+        func(input.clone());
+    }
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -69,99 +351,121 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    // NOTE: at least one iteration runs, so there are always samples.
+    (bench_iterations, BenchStats::from_samples(&timers).unwrap())
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
-}
-
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
-    } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+/// Try to submit one part of the solution if:
+///  1. `--submit <part>` was passed for this part.
+///  2. aoc-cli is installed, or the `client` feature is enabled.
+///  3. the answer has not been rejected before.
+///
+/// The verdict is recorded in the submission log. Correct answers are also stored as known answers.
+fn submit_result(result: &str, puzzle: PuzzleId, part: u8, options: &RunOptions) {
+    if options.submit != Some(part) {
+        return;
     }
-}
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
-    let is_intermediate_result = duration_str.is_empty();
+    let log = submissions::load(puzzle).unwrap_or_else(|e| {
+        eprintln!("Could not read submission log: {e}");
+        vec![]
+    });
 
-    match result {
-        Some(result) => {
-            if result.to_string().contains('\n') {
-                let str = format!("{part}: ▼ {duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
-                    println!("{result}");
-                }
-            } else {
-                let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
-                }
-            }
+    match submissions::check(&log, part, result) {
+        Check::PreviouslyRejected(verdict) => {
+            eprintln!("Refusing to submit \"{result}\": it was rejected before ({verdict}).");
+            return;
         }
-        None => {
-            if is_intermediate_result {
-                print!("{part}: ✖");
-            } else {
-                print!("\r");
-                println!("{part}: ✖             ");
-            }
+        Check::AboveBound(bound) => {
+            eprintln!("Warning: \"{result}\" is not lower than \"{bound}\", which was too high.");
         }
-    }
-}
-
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
-    let args: Vec<String> = env::args().collect();
-
-    if !args.contains(&"--submit".into()) {
-        return None;
+        Check::BelowBound(bound) => {
+            eprintln!("Warning: \"{result}\" is not higher than \"{bound}\", which was too low.");
+        }
+        Check::Ok => {}
     }
 
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+    if let Err(remaining) = throttle::wait_for_lockout(puzzle) {
+        eprintln!(
+            "Refusing to submit \"{result}\": the server asked to wait {} more before answering again.",
+            throttle::format_wait(remaining)
+        );
+        return;
     }
 
-    let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
-
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+    let Some((verdict, response)) = send_submission(result, puzzle, part) else {
+        return;
     };
 
-    if part_submit != part {
-        return None;
+    if let Some(wait) = throttle::parse_wait(&response) {
+        if let Err(e) = throttle::record_lockout(puzzle, wait) {
+            eprintln!("Could not record the time to wait: {e}");
+        }
     }
 
+    record_submission(puzzle, part, result, verdict);
+}
+
+/// Submits an answer via aoc-cli and returns the verdict and the response of the server.
+#[cfg(not(feature = "client"))]
+fn send_submission(result: &str, puzzle: PuzzleId, part: u8) -> Option<(Verdict, String)> {
     if aoc_cli::check().is_err() {
         eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+        std::process::exit(1);
     }
 
     println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+
+    match aoc_cli::submit(puzzle, part, result) {
+        Ok(output) => {
+            let response = String::from_utf8_lossy(&output.stdout).to_string();
+            Some((Verdict::from_response(&response), response))
+        }
+        Err(e) => {
+            eprintln!("failed to call aoc-cli: {e}");
+            None
+        }
+    }
+}
+
+/// Submits an answer with the built-in client and returns the verdict and the response of the server.
+#[cfg(feature = "client")]
+fn send_submission(result: &str, puzzle: PuzzleId, part: u8) -> Option<(Verdict, String)> {
+    use crate::template::aoc_client::Client;
+
+    println!("Submitting result...");
+
+    match Client::from_env().and_then(|client| client.submit(puzzle, part, result)) {
+        Ok(response) => {
+            println!("{}", response.message);
+            Some((response.verdict, response.message))
+        }
+        Err(e) => {
+            eprintln!("Failed to submit: {e}");
+            None
+        }
+    }
+}
+
+fn record_submission(puzzle: PuzzleId, part: u8, result: &str, verdict: Verdict) {
+    // answers that were not checked by the server are not worth recording.
+    if matches!(verdict, Verdict::TooRecent | Verdict::AlreadySolved) {
+        return;
+    }
+
+    if let Err(e) = submissions::record(puzzle, &Submission::new(part, result, verdict)) {
+        eprintln!("Could not write submission log: {e}");
+    }
+
+    if verdict == Verdict::Correct {
+        let saved = answers::load(puzzle).and_then(|mut known| {
+            known.set(part, result);
+            answers::save(puzzle, &known)
+        });
+
+        match saved {
+            Ok(()) => println!("Recorded answer in \"{}\".", answers::get_path(puzzle)),
+            Err(e) => eprintln!("Could not record answer: {e}"),
+        }
+    }
 }
@@ -0,0 +1,233 @@
+/// Templates for new solutions, used by `cargo scaffold`.
+///
+/// A template is a solution with placeholders: `{{year}}`, `{{day}}`, `{{title}}` and `{{answer}}`,
+/// the type parts return. Templates are read from `templates/<name>.rs`, so they can be edited or
+/// added. The templates shipped with the repository are built in as well, in case the folder is
+/// missing.
+use std::{fmt::Display, fs, io};
+
+use crate::template::markdown;
+use crate::PuzzleId;
+
+pub const TEMPLATE_DIR: &str = "templates";
+pub const DEFAULT_TEMPLATE: &str = "default";
+pub const DEFAULT_ANSWER_TYPE: &str = "u32";
+
+/// The types parts can return, see [`crate::template::solution::IntoAnswer`].
+pub const ANSWER_TYPES: &[&str] = &[
+    "u8", "u16", "u32", "u64", "usize", "i8", "i16", "i32", "i64", "i128", "isize", "String",
+];
+
+const BUILT_IN: &[(&str, &str)] = &[
+    ("default", include_str!("../../templates/default.rs")),
+    ("grid", include_str!("../../templates/grid.rs")),
+    ("lines", include_str!("../../templates/lines.rs")),
+    ("parse", include_str!("../../templates/parse.rs")),
+];
+
+#[derive(Debug)]
+pub enum Error {
+    NotFound(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::NotFound(name) => write!(
+                f,
+                "there is no template \"{name}\", available are: {}.",
+                available().join(", ")
+            ),
+            Error::IO(e) => write!(f, "could not read template: {e}"),
+        }
+    }
+}
+
+/// The values of the placeholders of a template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Placeholders {
+    pub puzzle: PuzzleId,
+    /// The title of the puzzle, e.g. `Day 1: Trebuchet?!`.
+    pub title: String,
+    pub answer: String,
+}
+
+impl Placeholders {
+    /// Takes the title from the puzzle description, if it was downloaded.
+    #[must_use]
+    pub fn new(puzzle: PuzzleId, answer: &str) -> Self {
+        let title = fs::read_to_string(puzzle.data_path("puzzles", "md"))
+            .ok()
+            .and_then(|description| markdown::title(&description))
+            .unwrap_or_else(|| format!("Day {}", puzzle.day.into_inner()));
+
+        Self {
+            puzzle,
+            title,
+            answer: answer.to_string(),
+        }
+    }
+}
+
+/// Reads a template from the templates folder, falling back to the built-in templates.
+pub fn load(name: &str) -> Result<String, Error> {
+    match fs::read_to_string(format!("{TEMPLATE_DIR}/{name}.rs")) {
+        Ok(template) => Ok(template),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => BUILT_IN
+            .iter()
+            .find(|(built_in, _)| *built_in == name)
+            .map(|(_, template)| (*template).to_string())
+            .ok_or_else(|| Error::NotFound(name.to_string())),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// The names of the built-in templates and those in the templates folder.
+#[must_use]
+pub fn available() -> Vec<String> {
+    let mut names: Vec<String> = BUILT_IN.iter().map(|(name, _)| name.to_string()).collect();
+
+    if let Ok(entries) = fs::read_dir(TEMPLATE_DIR) {
+        names.extend(entries.filter_map(|entry| {
+            let name = entry.ok()?.file_name().into_string().ok()?;
+            name.strip_suffix(".rs").map(String::from)
+        }));
+    }
+
+    names.sort();
+    names.dedup();
+    names
+}
+
+#[must_use]
+pub fn render(template: &str, placeholders: &Placeholders) -> String {
+    template
+        .replace("{{year}}", &placeholders.puzzle.year.to_string())
+        .replace("{{day}}", &placeholders.puzzle.day.into_inner().to_string())
+        .replace("{{title}}", &placeholders.title)
+        .replace("{{answer}}", &placeholders.answer)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, fs, path::Path, process::Command};
+
+    use super::{render, Placeholders, ANSWER_TYPES, BUILT_IN};
+    use crate::template::solution::IntoAnswer;
+    use crate::{day, year, PuzzleId};
+
+    /// Checks that parts can return each type, and lists their names.
+    macro_rules! answer_types {
+        ($($t:ty),*) => {{
+            $(assert!(Some(<$t>::default()).into_answer().is_ok());)*
+            vec![$(stringify!($t)),*]
+        }};
+    }
+
+    #[test]
+    fn offers_answer_types_parts_can_return() {
+        let types = answer_types!(u8, u16, u32, u64, usize, i8, i16, i32, i64, i128, isize, String);
+        assert_eq!(types, ANSWER_TYPES);
+
+        for answer in ANSWER_TYPES {
+            let placeholders = Placeholders {
+                puzzle: PuzzleId::new(year!(2023), day!(5)),
+                title: "Day 5".into(),
+                answer: (*answer).to_string(),
+            };
+
+            for (name, template) in BUILT_IN {
+                assert!(
+                    render(template, &placeholders).contains(&format!("Option<{answer}>")),
+                    "template {name} does not return {answer}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn renders_placeholders() {
+        let placeholders = Placeholders {
+            puzzle: PuzzleId::new(year!(2023), day!(5)),
+            title: "Day 5: If You Give A Seed A Fertilizer".into(),
+            answer: "u64".into(),
+        };
+
+        for (name, template) in BUILT_IN {
+            let rendered = render(template, &placeholders);
+
+            assert!(
+                !rendered.contains("{{"),
+                "template {name} has unknown placeholders"
+            );
+            assert!(
+                rendered.starts_with(
+                    "// Day 5: If You Give A Seed A Fertilizer\nadvent_of_code::solution!(2023, 5"
+                ),
+                "template {name} has no header"
+            );
+            assert!(rendered.contains("Option<u64>"));
+        }
+    }
+
+    /// Scaffolds every built-in template into a scratch crate and lints it. Parts of a fresh
+    /// scaffold do not use their input yet, so only unused variables are allowed.
+    #[test]
+    fn scaffolds_lint_free_solutions() {
+        let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let crate_dir = manifest_dir.join("target/template-check");
+        let bin_dir = crate_dir.join("src/bin");
+        let _ = fs::remove_dir_all(&bin_dir);
+        fs::create_dir_all(&bin_dir).unwrap();
+
+        let manifest = fs::read_to_string(manifest_dir.join("Cargo.toml")).unwrap();
+        let dependencies = &manifest[manifest.find("[dependencies]").unwrap()..];
+        fs::write(
+            crate_dir.join("Cargo.toml"),
+            format!(
+                "[package]\nname = \"template-check\"\nedition = \"2021\"\npublish = false\n\n[workspace]\n\n{dependencies}advent_of_code = {{ path = {:?} }}\n",
+                manifest_dir.display()
+            ),
+        )
+        .unwrap();
+        // resolve the same versions as the template itself.
+        let _ = fs::copy(
+            manifest_dir.join("Cargo.lock"),
+            crate_dir.join("Cargo.lock"),
+        );
+
+        let placeholders = Placeholders {
+            puzzle: PuzzleId::new(year!(2023), day!(5)),
+            title: "Day 5".into(),
+            answer: "u32".into(),
+        };
+
+        for (name, template) in BUILT_IN {
+            fs::write(
+                bin_dir.join(format!("{name}.rs")),
+                render(template, &placeholders),
+            )
+            .unwrap();
+        }
+
+        let output = Command::new(env::var("CARGO").unwrap_or_else(|_| "cargo".into()))
+            .args(["clippy", "--quiet", "--all-targets", "--"])
+            .args(["-D", "warnings", "-A", "unused_variables"])
+            .current_dir(&crate_dir)
+            .output()
+            .unwrap();
+
+        assert!(
+            output.status.success(),
+            "scaffolded templates do not pass clippy:\n{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+}
@@ -0,0 +1,327 @@
+/// A trait-based alternative to the free functions wired up by the `solution!` macro, and the
+/// answer types shared by both forms.
+///
+/// Implementors are registered with `advent_of_code::solution!(impl MySolution)`, which sets up
+/// the `main` function and the dispatch table entry like the free-function form does.
+use std::{fmt::Display, num::TryFromIntError, time::Duration};
+
+use crate::PuzzleId;
+
+/// The answer to a puzzle part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    /// An integer of any width up to `i128`.
+    Integer(i128),
+    Text(String),
+    /// A multi-line answer, e.g. letters drawn on a grid. Each entry is one row.
+    Grid(Vec<String>),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Integer(value) => write!(f, "{value}"),
+            Answer::Text(value) => f.write_str(value),
+            Answer::Grid(rows) => f.write_str(&rows.join("\n")),
+        }
+    }
+}
+
+macro_rules! impl_from_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Answer::Integer(i128::from(value))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_integer!(u8, u16, u32, u64, i8, i16, i32, i64, i128);
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        // usize is at most 64 bits wide on every supported platform.
+        Answer::Integer(value as i128)
+    }
+}
+
+impl From<isize> for Answer {
+    fn from(value: isize) -> Self {
+        Answer::Integer(value as i128)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+impl From<Vec<Vec<char>>> for Answer {
+    fn from(grid: Vec<Vec<char>>) -> Self {
+        Answer::Grid(grid.into_iter().map(String::from_iter).collect())
+    }
+}
+
+/// Why a part did not produce an answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The part has not been solved yet.
+    Unsolved,
+    /// The part failed with a message.
+    Failed(String),
+    /// The answer or an intermediate value did not fit into its type.
+    Overflow,
+    /// The part panicked. Set by the runner.
+    Panicked(String),
+    /// The first execution of the part took longer than the timeout. Set by the runner.
+    TimedOut(Duration),
+}
+
+impl From<TryFromIntError> for Error {
+    fn from(_: TryFromIntError) -> Self {
+        Error::Overflow
+    }
+}
+
+impl From<String> for Error {
+    fn from(message: String) -> Self {
+        Error::Failed(message)
+    }
+}
+
+impl From<&str> for Error {
+    fn from(message: &str) -> Self {
+        Error::Failed(message.to_string())
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Unsolved => f.write_str("not solved yet"),
+            Error::Failed(message) => f.write_str(message),
+            Error::Overflow => f.write_str("arithmetic overflow"),
+            Error::Panicked(message) => write!(f, "panicked: {message}"),
+            Error::TimedOut(timeout) => write!(f, "timed out after {timeout:.1?}"),
+        }
+    }
+}
+
+/// Return types of solution parts the runner accepts. `None` counts as unsolved.
+pub trait IntoAnswer {
+    fn into_answer(self) -> Result<Answer, Error>;
+}
+
+impl<T: Into<Answer>> IntoAnswer for Option<T> {
+    fn into_answer(self) -> Result<Answer, Error> {
+        self.map(Into::into).ok_or(Error::Unsolved)
+    }
+}
+
+impl<T: Into<Answer>, E: Into<Error>> IntoAnswer for Result<T, E> {
+    fn into_answer(self) -> Result<Answer, Error> {
+        self.map(Into::into).map_err(Into::into)
+    }
+}
+
+/// A solution of a puzzle, as an alternative to free `part_one` and `part_two` functions.
+///
+/// ```ignore
+/// use advent_of_code::template::{solution::Error, Solution};
+/// use advent_of_code::{day, year, PuzzleId};
+///
+/// advent_of_code::solution!(impl Trebuchet);
+///
+/// pub struct Trebuchet;
+///
+/// impl Solution for Trebuchet {
+///     const PUZZLE: PuzzleId = PuzzleId::new(year!(2023), day!(1));
+///     const NAME: Option<&'static str> = Some("Trebuchet?!");
+///
+///     type Parsed<'a> = Vec<&'a str>;
+///     type Answer1 = usize;
+///     type Answer2 = u32;
+///
+///     fn parse(input: &str) -> Self::Parsed<'_> {
+///         input.lines().collect()
+///     }
+///
+///     fn part_one(lines: &Self::Parsed<'_>) -> Result<usize, Error> {
+///         Ok(lines.len())
+///     }
+///
+///     fn part_two(_lines: &Self::Parsed<'_>) -> Result<u32, Error> {
+///         Err(Error::Unsolved)
+///     }
+/// }
+///
+/// assert_eq!(Trebuchet::solve_part_one("a\nb"), Ok(2));
+/// ```
+pub trait Solution {
+    /// The puzzle this solution solves.
+    const PUZZLE: PuzzleId;
+
+    /// A human-readable name, e.g. the title of the puzzle. Shown by `cargo all`.
+    const NAME: Option<&'static str> = None;
+
+    /// Whether parsing is timed and reported as a separate step. If turned off, each part parses
+    /// the input itself and the parse time counts towards the part. Solutions whose `parse` does
+    /// no real work, e.g. returns the input as is, can turn this off.
+    const SEPARATE_PARSE: bool = true;
+
+    /// The input shared by both parts. It may borrow from the raw input.
+    type Parsed<'a>;
+    type Answer1: Into<Answer>;
+    type Answer2: Into<Answer>;
+
+    fn parse(input: &str) -> Self::Parsed<'_>;
+
+    fn part_one(input: &Self::Parsed<'_>) -> Result<Self::Answer1, Error>;
+
+    fn part_two(input: &Self::Parsed<'_>) -> Result<Self::Answer2, Error>;
+
+    /// Parses the raw input and solves part one. Useful in tests.
+    fn solve_part_one(input: &str) -> Result<Self::Answer1, Error> {
+        Self::part_one(&Self::parse(input))
+    }
+
+    /// Parses the raw input and solves part two. Useful in tests.
+    fn solve_part_two(input: &str) -> Result<Self::Answer2, Error> {
+        Self::part_two(&Self::parse(input))
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Answer, Error, IntoAnswer, Solution};
+    use crate::template::runner::{run_solution, RunOptions};
+    use crate::{day, year, PuzzleId};
+
+    struct Sum;
+
+    impl Solution for Sum {
+        const PUZZLE: PuzzleId = PuzzleId::new(year!(2023), day!(1));
+        type Parsed<'a> = Vec<&'a str>;
+        type Answer1 = u32;
+        type Answer2 = String;
+
+        fn parse(input: &str) -> Self::Parsed<'_> {
+            input.lines().collect()
+        }
+
+        fn part_one(input: &Self::Parsed<'_>) -> Result<u32, Error> {
+            input
+                .iter()
+                .map(|line| line.parse::<u32>().map_err(|e| e.to_string().into()))
+                .sum()
+        }
+
+        fn part_two(input: &Self::Parsed<'_>) -> Result<String, Error> {
+            input
+                .last()
+                .map(|line| line.to_string())
+                .ok_or(Error::Unsolved)
+        }
+    }
+
+    #[test]
+    fn solves_parts_from_raw_input() {
+        assert_eq!(Sum::solve_part_one("1\n2\n3"), Ok(6));
+        assert_eq!(Sum::solve_part_two("1\n2\n3"), Ok("3".into()));
+        assert_eq!(
+            Sum::solve_part_one("1\nx"),
+            Err(Error::Failed("invalid digit found in string".into()))
+        );
+    }
+
+    #[test]
+    fn runs_solutions() {
+        let options = RunOptions {
+            quiet: true,
+            ..RunOptions::default()
+        };
+
+        let report = run_solution::<Sum>("1\n2\n3", &options);
+        assert!(report.parse.is_some());
+        assert_eq!(report.parts.len(), 2);
+        assert_eq!(report.parts[0].answer.as_deref(), Some("6"));
+        assert_eq!(report.parts[1].answer.as_deref(), Some("3"));
+    }
+
+    /// Parses as part of the parts, and panics on empty input.
+    struct Lines;
+
+    impl Solution for Lines {
+        const PUZZLE: PuzzleId = PuzzleId::new(year!(2023), day!(2));
+        const SEPARATE_PARSE: bool = false;
+        type Parsed<'a> = Vec<&'a str>;
+        type Answer1 = usize;
+        type Answer2 = usize;
+
+        fn parse(input: &str) -> Self::Parsed<'_> {
+            assert!(!input.is_empty(), "empty input");
+            input.lines().collect()
+        }
+
+        fn part_one(input: &Self::Parsed<'_>) -> Result<usize, Error> {
+            Ok(input.len())
+        }
+
+        fn part_two(input: &Self::Parsed<'_>) -> Result<usize, Error> {
+            Ok(input.len() * 2)
+        }
+    }
+
+    #[test]
+    fn reports_panicking_parse_in_parts_as_crashed() {
+        let options = RunOptions {
+            quiet: true,
+            ..RunOptions::default()
+        };
+
+        let report = run_solution::<Lines>("a\nb", &options);
+        assert!(report.parse.is_none());
+        assert_eq!(report.parts[1].answer.as_deref(), Some("4"));
+
+        let report = run_solution::<Lines>("", &options);
+        assert_eq!(report.parts.len(), 2);
+        for part in &report.parts {
+            assert_eq!(part.error, Some(Error::Panicked("empty input".into())));
+        }
+    }
+
+    #[test]
+    fn converts_answers() {
+        assert_eq!(Answer::from(u64::MAX).to_string(), "18446744073709551615");
+        assert_eq!(Answer::from(-3_i8).to_string(), "-3");
+        assert_eq!(Answer::from("abc").to_string(), "abc");
+        assert_eq!(
+            Answer::from(vec![vec!['#', '.'], vec!['.', '#']]).to_string(),
+            "#.\n.#"
+        );
+    }
+
+    #[test]
+    fn converts_part_results() {
+        assert_eq!(Some(42_u32).into_answer(), Ok(Answer::Integer(42)));
+        assert_eq!(None::<u32>.into_answer(), Err(Error::Unsolved));
+        assert_eq!(
+            u32::try_from(u64::MAX).map_err(Error::from).into_answer(),
+            Err(Error::Overflow)
+        );
+        assert_eq!(
+            Err::<u32, _>("no start tile").into_answer(),
+            Err(Error::Failed("no start tile".into()))
+        );
+    }
+}
@@ -0,0 +1,310 @@
+/// Keeps a log of submitted answers in `data/<year>/submissions/<day>.log`.
+///
+/// Each line of the log holds one submission as tab-separated fields:
+/// `<unix timestamp>\t<part>\t<verdict>\t<answer>`, with tabs, newlines and backslashes in the
+/// answer escaped.
+use std::{
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::template::create_parent_dir;
+use crate::PuzzleId;
+
+/// The response of the puzzle server to a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+    /// An answer was submitted too recently, the answer was not checked.
+    TooRecent,
+    /// The part has been solved already, the answer was not checked.
+    AlreadySolved,
+    Unknown,
+}
+
+impl Verdict {
+    /// Parses the verdict from the text of a submission response.
+    #[must_use]
+    pub fn from_response(response: &str) -> Self {
+        if response.contains("That's the right answer") {
+            Verdict::Correct
+        } else if response.contains("answer is too high") {
+            Verdict::TooHigh
+        } else if response.contains("answer is too low") {
+            Verdict::TooLow
+        } else if response.contains("That's not the right answer") {
+            Verdict::Incorrect
+        } else if response.contains("You gave an answer too recently") {
+            Verdict::TooRecent
+        } else if response.contains("Did you already complete it") {
+            Verdict::AlreadySolved
+        } else {
+            Verdict::Unknown
+        }
+    }
+
+    /// Whether the server checked the answer and rejected it.
+    #[must_use]
+    pub fn is_rejection(self) -> bool {
+        matches!(
+            self,
+            Verdict::Incorrect | Verdict::TooHigh | Verdict::TooLow
+        )
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Verdict::Correct => "correct",
+            Verdict::Incorrect => "incorrect",
+            Verdict::TooHigh => "too_high",
+            Verdict::TooLow => "too_low",
+            Verdict::TooRecent => "too_recent",
+            Verdict::AlreadySolved => "already_solved",
+            Verdict::Unknown => "unknown",
+        };
+        f.write_str(s)
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Verdict::Correct),
+            "incorrect" => Ok(Verdict::Incorrect),
+            "too_high" => Ok(Verdict::TooHigh),
+            "too_low" => Ok(Verdict::TooLow),
+            "too_recent" => Ok(Verdict::TooRecent),
+            "already_solved" => Ok(Verdict::AlreadySolved),
+            "unknown" => Ok(Verdict::Unknown),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Submission {
+    pub timestamp: u64,
+    pub part: u8,
+    pub verdict: Verdict,
+    pub answer: String,
+}
+
+impl Submission {
+    #[must_use]
+    pub fn new(part: u8, answer: &str, verdict: Verdict) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+
+        Self {
+            timestamp,
+            part,
+            verdict,
+            answer: answer.to_string(),
+        }
+    }
+
+    fn to_line(&self) -> String {
+        format!(
+            "{}\t{}\t{}\t{}",
+            self.timestamp,
+            self.part,
+            self.verdict,
+            escape(&self.answer)
+        )
+    }
+
+    fn from_line(line: &str) -> Option<Self> {
+        let mut fields = line.splitn(4, '\t');
+        Some(Self {
+            timestamp: fields.next()?.parse().ok()?,
+            part: fields.next()?.parse().ok()?,
+            verdict: fields.next()?.parse().ok()?,
+            answer: unescape(fields.next()?),
+        })
+    }
+}
+
+/// Why an answer should not be submitted, or what to be careful about.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Check {
+    Ok,
+    /// The exact answer was rejected before.
+    PreviouslyRejected(Verdict),
+    /// The answer is not below a known "too high" bound.
+    AboveBound(String),
+    /// The answer is not above a known "too low" bound.
+    BelowBound(String),
+}
+
+#[must_use]
+pub fn get_path(puzzle: PuzzleId) -> String {
+    puzzle.data_path("submissions", "log")
+}
+
+/// Reads all logged submissions for a puzzle. A missing log yields no submissions.
+pub fn load(puzzle: PuzzleId) -> io::Result<Vec<Submission>> {
+    match fs::read_to_string(get_path(puzzle)) {
+        Ok(content) => Ok(parse(&content)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(vec![]),
+        Err(e) => Err(e),
+    }
+}
+
+/// Appends a submission to the log of a puzzle.
+pub fn record(puzzle: PuzzleId, submission: &Submission) -> io::Result<()> {
+    let path = get_path(puzzle);
+    create_parent_dir(&path)?;
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", submission.to_line())
+}
+
+/// Checks a new answer for a part against previous submissions.
+#[must_use]
+pub fn check(submissions: &[Submission], part: u8, answer: &str) -> Check {
+    let previous = submissions.iter().filter(|s| s.part == part);
+
+    if let Some(rejected) = previous
+        .clone()
+        .find(|s| s.answer == answer && s.verdict.is_rejection())
+    {
+        return Check::PreviouslyRejected(rejected.verdict);
+    }
+
+    let Ok(value) = answer.parse::<i128>() else {
+        return Check::Ok;
+    };
+
+    for submission in previous {
+        let Ok(bound) = submission.answer.parse::<i128>() else {
+            continue;
+        };
+
+        match submission.verdict {
+            Verdict::TooHigh if value >= bound => {
+                return Check::AboveBound(submission.answer.clone())
+            }
+            Verdict::TooLow if value <= bound => {
+                return Check::BelowBound(submission.answer.clone())
+            }
+            _ => {}
+        }
+    }
+
+    Check::Ok
+}
+
+fn parse(content: &str) -> Vec<Submission> {
+    content.lines().filter_map(Submission::from_line).collect()
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+}
+
+fn unescape(s: &str) -> String {
+    let mut value = String::with_capacity(s.len());
+    let mut chars = s.chars();
+
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('\\', Some('t')) => value.push('\t'),
+            ('\\', Some('n')) => value.push('\n'),
+            ('\\', Some('\\')) => value.push('\\'),
+            (c, _) => {
+                value.push(c);
+                continue;
+            }
+        }
+        chars.next();
+    }
+
+    value
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{check, parse, Check, Submission, Verdict};
+
+    fn submission(part: u8, answer: &str, verdict: Verdict) -> Submission {
+        Submission {
+            timestamp: 1_701_388_800,
+            part,
+            verdict,
+            answer: answer.into(),
+        }
+    }
+
+    #[test]
+    fn parses_verdicts() {
+        assert_eq!(
+            Verdict::from_response("That's the right answer! You are one gold star closer."),
+            Verdict::Correct
+        );
+        assert_eq!(
+            Verdict::from_response("That's not the right answer; your answer is too high."),
+            Verdict::TooHigh
+        );
+        assert_eq!(
+            Verdict::from_response("That's not the right answer; your answer is too low."),
+            Verdict::TooLow
+        );
+        assert_eq!(
+            Verdict::from_response("That's not the right answer."),
+            Verdict::Incorrect
+        );
+        assert_eq!(
+            Verdict::from_response("You gave an answer too recently; you have 45s left to wait."),
+            Verdict::TooRecent
+        );
+        assert_eq!(Verdict::from_response(""), Verdict::Unknown);
+    }
+
+    #[test]
+    fn round_trips_log_lines() {
+        let s = submission(2, "a\tb\\n\nc", Verdict::TooLow);
+        assert_eq!(parse(&format!("{}\n", s.to_line())), vec![s]);
+    }
+
+    #[test]
+    fn refuses_rejected_answers() {
+        let log = [
+            submission(1, "100", Verdict::Incorrect),
+            submission(2, "100", Verdict::TooHigh),
+        ];
+        assert_eq!(
+            check(&log, 1, "100"),
+            Check::PreviouslyRejected(Verdict::Incorrect)
+        );
+        assert_eq!(
+            check(&log, 2, "100"),
+            Check::PreviouslyRejected(Verdict::TooHigh)
+        );
+        assert_eq!(check(&log, 1, "101"), Check::Ok);
+    }
+
+    #[test]
+    fn warns_about_known_bounds() {
+        let log = [
+            submission(1, "100", Verdict::TooHigh),
+            submission(1, "10", Verdict::TooLow),
+        ];
+        assert_eq!(check(&log, 1, "150"), Check::AboveBound("100".into()));
+        assert_eq!(check(&log, 1, "5"), Check::BelowBound("10".into()));
+        assert_eq!(check(&log, 1, "50"), Check::Ok);
+        assert_eq!(check(&log, 2, "150"), Check::Ok);
+    }
+}
@@ -0,0 +1,269 @@
+/// Runs solutions on a worker thread, so steps that exceed their timeout can be abandoned.
+///
+/// Threads can not be killed: an abandoned step keeps running in the background until the process
+/// exits, but its output is discarded. The remaining parts continue on a fresh worker thread.
+/// Only the first execution of a step is limited, benching a step that finished in time is not.
+///
+/// Runs can also be buffered, to print the output of runs on several threads in order.
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::template::registry::Entry;
+use crate::template::report::{PartReport, RunReport};
+use crate::template::runner::{self, Event, RunOptions, Step};
+use crate::template::solution::Error;
+
+/// Runs a solution, enforcing [`RunOptions::timeout`] if set. Panics are isolated by the runner
+/// in either case.
+#[must_use]
+pub fn run(entry: &Entry, input: &str, options: &RunOptions) -> RunReport {
+    if options.timeout.is_none() {
+        return (entry.run)(input, options);
+    }
+
+    supervise(entry, input, options, &mut |event| {
+        runner::print_event(event, options);
+    })
+}
+
+/// Like [`run`], but collects the final output of each step instead of printing it, so runs on
+/// several threads can be printed in order with [`runner::print_event`] afterwards.
+#[must_use]
+pub fn run_buffered(entry: &Entry, input: &str, options: &RunOptions) -> (RunReport, Vec<Event>) {
+    let mut events = vec![];
+
+    let report = supervise(entry, input, options, &mut |event| {
+        if matches!(event, Event::Parsed(_) | Event::Finished(_)) {
+            events.push(event.clone());
+        }
+    });
+
+    (report, events)
+}
+
+fn supervise(
+    entry: &Entry,
+    input: &str,
+    options: &RunOptions,
+    on_event: &mut dyn FnMut(&Event),
+) -> RunReport {
+    let mut report = RunReport::default();
+    let mut pending: Vec<u8> = [1, 2]
+        .into_iter()
+        .filter(|part| options.runs_part(*part))
+        .collect();
+
+    while let Some(&next) = pending.first() {
+        let worker_options = RunOptions {
+            // after a timeout, the worker only runs the parts that are left.
+            part: if pending.len() == 1 {
+                Some(next)
+            } else {
+                options.part
+            },
+            ..*options
+        };
+
+        run_worker(
+            entry,
+            input,
+            &worker_options,
+            on_event,
+            &mut report,
+            &mut pending,
+        );
+    }
+
+    report.parts.sort_by_key(|part| part.part);
+    report
+}
+
+/// Runs a solution on a new thread until it finished or a step timed out.
+fn run_worker(
+    entry: &Entry,
+    input: &str,
+    options: &RunOptions,
+    on_event: &mut dyn FnMut(&Event),
+    report: &mut RunReport,
+    pending: &mut Vec<u8>,
+) {
+    let (sender, receiver) = mpsc::channel();
+    let run = entry.run;
+    let input = input.to_string();
+    let worker_options = *options;
+
+    thread::spawn(move || {
+        runner::forward_events(sender);
+        run(&input, &worker_options);
+    });
+
+    let mut current: Option<(Step, Instant)> = None;
+
+    loop {
+        let event = match (current, options.timeout) {
+            (Some((_, started)), Some(timeout)) => {
+                receiver.recv_timeout((started + timeout).saturating_duration_since(Instant::now()))
+            }
+            _ => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+
+        match event {
+            Ok(event) => {
+                match &event {
+                    Event::Started(step) => current = Some((*step, Instant::now())),
+                    Event::Answered(..) => current = None,
+                    // a worker that continues after a timeout parses the input again.
+                    Event::Parsed(_) if report.parse.is_some() => continue,
+                    Event::Parsed(parse) => report.parse = Some(parse.clone()),
+                    Event::Finished(part) => {
+                        current = None;
+                        pending.retain(|p| *p != part.part);
+                        report.parts.push(part.clone());
+                    }
+                }
+
+                on_event(&event);
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                // only reachable with a timeout set.
+                let timeout = options.timeout.unwrap_or_default();
+
+                // a timed out parse step leaves nothing to run the parts on.
+                let timed_out: Vec<u8> = match current {
+                    Some((Step::Part(part), _)) => vec![part],
+                    _ => pending.clone(),
+                };
+
+                fail_parts(
+                    entry,
+                    &timed_out,
+                    &Error::TimedOut(timeout),
+                    timeout,
+                    on_event,
+                    report,
+                    pending,
+                );
+                return;
+            }
+            Err(RecvTimeoutError::Disconnected) => {
+                // the runner catches panics, so this only happens if the worker died otherwise.
+                let stopped = Error::Failed("the worker thread stopped unexpectedly".into());
+                let remaining = pending.clone();
+                fail_parts(
+                    entry,
+                    &remaining,
+                    &stopped,
+                    Duration::ZERO,
+                    on_event,
+                    report,
+                    pending,
+                );
+                return;
+            }
+        }
+    }
+}
+
+fn fail_parts(
+    entry: &Entry,
+    parts: &[u8],
+    error: &Error,
+    duration: Duration,
+    on_event: &mut dyn FnMut(&Event),
+    report: &mut RunReport,
+    pending: &mut Vec<u8>,
+) {
+    for &part in parts {
+        let failed = PartReport::failed(entry.puzzle, part, error.clone(), duration);
+        on_event(&Event::Finished(failed.clone()));
+        pending.retain(|p| *p != part);
+        report.parts.push(failed);
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::thread;
+    use std::time::Duration;
+
+    use super::{run, run_buffered};
+    use crate::template::registry::Entry;
+    use crate::template::report::PartStatus;
+    use crate::template::runner::{Event, RunOptions};
+    use crate::template::solution::{Error, Solution};
+    use crate::{day, year, PuzzleId};
+
+    struct Stuck;
+
+    impl Solution for Stuck {
+        const PUZZLE: PuzzleId = PuzzleId::new(year!(2023), day!(5));
+        type Parsed<'a> = &'a str;
+        type Answer1 = u32;
+        type Answer2 = u32;
+
+        fn parse(input: &str) -> Self::Parsed<'_> {
+            input
+        }
+
+        fn part_one(_input: &Self::Parsed<'_>) -> Result<u32, Error> {
+            thread::sleep(Duration::from_secs(10));
+            Ok(1)
+        }
+
+        fn part_two(input: &Self::Parsed<'_>) -> Result<u32, Error> {
+            match *input {
+                "panic" => panic!("boom"),
+                _ => Ok(2),
+            }
+        }
+    }
+
+    fn options(timeout: Option<Duration>) -> RunOptions {
+        RunOptions {
+            quiet: true,
+            timeout,
+            ..RunOptions::default()
+        }
+    }
+
+    #[test]
+    fn abandons_parts_that_time_out() {
+        let entry = Entry::of::<Stuck>();
+        let report = run(&entry, "", &options(Some(Duration::from_millis(50))));
+
+        assert!(report.parse.is_some());
+        assert_eq!(report.parts.len(), 2);
+        assert_eq!(report.parts[0].status(), PartStatus::TimedOut);
+        assert_eq!(report.parts[1].answer.as_deref(), Some("2"));
+    }
+
+    #[test]
+    fn isolates_panics() {
+        let entry = Entry::of::<Stuck>();
+        let options = RunOptions {
+            part: Some(2),
+            ..options(None)
+        };
+        let report = run(&entry, "panic", &options);
+
+        assert_eq!(report.parts.len(), 1);
+        assert_eq!(report.parts[0].status(), PartStatus::Crashed);
+        assert_eq!(report.parts[0].error, Some(Error::Panicked("boom".into())));
+    }
+
+    #[test]
+    fn buffers_final_output() {
+        let entry = Entry::of::<Stuck>();
+        let options = RunOptions {
+            part: Some(2),
+            ..options(None)
+        };
+        let (report, events) = run_buffered(&entry, "", &options);
+
+        assert_eq!(report.parts.len(), 1);
+        assert!(
+            matches!(events.as_slice(), [Event::Parsed(_), Event::Finished(part)] if part.part == 2)
+        );
+    }
+}
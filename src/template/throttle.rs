@@ -0,0 +1,178 @@
+/// Keeps requests to the puzzle server at a polite pace.
+///
+/// Every request waits until [`MIN_INTERVAL`] has passed since the previous one, which is recorded
+/// in `data/.cache/last_request`. When the server asks to wait before answering again, e.g. after
+/// a wrong answer, the time is recorded per puzzle in `data/.cache/<year>/<day>-locked-until`, and
+/// the next submission waits for it or is refused.
+use std::{
+    fs, io,
+    sync::LazyLock,
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use regex::Regex;
+
+use crate::template::config;
+use crate::template::write_file;
+use crate::PuzzleId;
+
+/// The minimum time between two requests.
+pub const MIN_INTERVAL: Duration = Duration::from_secs(5);
+/// Submissions that are locked for longer than this are refused instead of waited for.
+pub const MAX_SLEEP: Duration = Duration::from_secs(60);
+
+/// Relative to the data directory.
+const LAST_REQUEST_PATH: &str = ".cache/last_request";
+
+static WAIT: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait|wait (one|\d+) minutes?")
+        .expect("valid regex")
+});
+
+/// Sleeps until `interval` has passed since the last request, then records this one.
+pub fn wait(interval: Duration) {
+    if interval.is_zero() {
+        return;
+    }
+
+    let path = config::get().data_path(LAST_REQUEST_PATH);
+    let last = fs::read_to_string(&path)
+        .ok()
+        .and_then(|millis| millis.trim().parse().ok());
+
+    if let Some(last) = last {
+        let delay = delay(last, now(), interval);
+
+        if !delay.is_zero() {
+            thread::sleep(delay);
+        }
+    }
+
+    // a missing record only makes the next request less patient.
+    let _ = write_file(&path, now().to_string());
+}
+
+/// Waits for the lock of a puzzle if it ends within [`MAX_SLEEP`]. Returns the remaining time if
+/// it does not.
+pub fn wait_for_lockout(puzzle: PuzzleId) -> Result<(), Duration> {
+    let locked_until = fs::read_to_string(lockout_path(puzzle))
+        .ok()
+        .and_then(|millis| millis.trim().parse::<u64>().ok());
+
+    let Some(locked_until) = locked_until else {
+        return Ok(());
+    };
+
+    let remaining = Duration::from_millis(locked_until.saturating_sub(now()));
+
+    if remaining > MAX_SLEEP {
+        return Err(remaining);
+    }
+
+    if !remaining.is_zero() {
+        println!(
+            "Waiting {} before submitting, as asked by the server...",
+            format_wait(remaining)
+        );
+        thread::sleep(remaining);
+    }
+
+    Ok(())
+}
+
+/// Records the time the server asked to wait before answering a puzzle again.
+pub fn record_lockout(puzzle: PuzzleId, wait: Duration) -> io::Result<()> {
+    let until = now() + u64::try_from(wait.as_millis()).unwrap_or(u64::MAX);
+    write_file(lockout_path(puzzle), until.to_string())
+}
+
+/// Parses the time to wait from a submission response, e.g. `You have 4m 32s left to wait` or
+/// `please wait one minute before trying again`.
+#[must_use]
+pub fn parse_wait(response: &str) -> Option<Duration> {
+    let captures = WAIT.captures(response)?;
+    let number = |index: usize| -> u64 {
+        captures
+            .get(index)
+            .map_or(0, |m| m.as_str().parse().unwrap_or(1))
+    };
+
+    let seconds = match captures.get(3) {
+        Some(_) => number(3) * 60,
+        None => number(1) * 60 + number(2),
+    };
+
+    Some(Duration::from_secs(seconds))
+}
+
+/// Formats a wait time like the server does, e.g. `4m 32s`.
+#[must_use]
+pub fn format_wait(wait: Duration) -> String {
+    let seconds = wait.as_secs_f64().ceil() as u64;
+
+    match seconds / 60 {
+        0 => format!("{seconds}s"),
+        minutes => format!("{minutes}m {}s", seconds % 60),
+    }
+}
+
+#[must_use]
+pub fn lockout_path(puzzle: PuzzleId) -> String {
+    config::get().data_path(&format!(
+        ".cache/{}/{}-locked-until",
+        puzzle.year, puzzle.day
+    ))
+}
+
+/// The time left until `interval` has passed since `since`, both in milliseconds.
+fn delay(since: u64, now: u64, interval: Duration) -> Duration {
+    let elapsed = Duration::from_millis(now.saturating_sub(since));
+    interval.saturating_sub(elapsed)
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| u64::try_from(d.as_millis()).unwrap_or(u64::MAX))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{delay, format_wait, parse_wait};
+
+    #[test]
+    fn parses_wait_times() {
+        assert_eq!(
+            parse_wait("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 4m 32s left to wait."),
+            Some(Duration::from_secs(272))
+        );
+        assert_eq!(
+            parse_wait("You have 38s left to wait."),
+            Some(Duration::from_secs(38))
+        );
+        assert_eq!(
+            parse_wait("That's not the right answer. Please wait one minute before trying again."),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(
+            parse_wait("Because you have guessed incorrectly 4 times on this puzzle, please wait 5 minutes before trying again."),
+            Some(Duration::from_secs(300))
+        );
+        assert_eq!(parse_wait("That's the right answer!"), None);
+    }
+
+    #[test]
+    fn computes_delays() {
+        let interval = Duration::from_secs(5);
+
+        assert_eq!(delay(1_000, 3_000, interval), Duration::from_secs(3));
+        assert_eq!(delay(1_000, 9_000, interval), Duration::ZERO);
+        // a clock that went backwards does not block forever.
+        assert_eq!(delay(9_000, 1_000, interval), interval);
+        assert_eq!(format_wait(Duration::from_millis(272_400)), "4m 33s");
+        assert_eq!(format_wait(Duration::from_secs(38)), "38s");
+    }
+}
@@ -0,0 +1,183 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// The first year Advent of Code took place.
+pub const FIRST_YEAR: u16 = 2015;
+
+/// The name of the environment variable that holds the default year.
+pub const YEAR_ENV_VAR: &str = "AOC_YEAR";
+
+/// A valid year of Advent of Code (i.e. an integer of 2015 or later).
+///
+/// # Display
+/// This value displays as a four digit number.
+///
+/// ```
+/// # use advent_of_code::Year;
+/// let year = Year::new(2023).unwrap();
+/// assert_eq!(year.to_string(), "2023")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// Creates a [`Year`] from the provided value if it's in the valid range,
+    /// returns [`None`] otherwise.
+    pub fn new(year: u16) -> Option<Self> {
+        if !(FIRST_YEAR..=9999).contains(&year) {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    /// Reads the default [`Year`] from the `AOC_YEAR` environment variable.
+    pub fn from_env() -> Result<Self, YearFromEnvError> {
+        let value = std::env::var(YEAR_ENV_VAR).map_err(|_| YearFromEnvError::Missing)?;
+        value.parse().map_err(|_| YearFromEnvError::Invalid(value))
+    }
+
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __new_unchecked(year: u16) -> Self {
+        Self(year)
+    }
+
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __parse_const(s: &str) -> Self {
+        let bytes = s.as_bytes();
+        assert!(bytes.len() == 4, "expecting a four digit year");
+
+        let mut year: u16 = 0;
+        let mut i = 0;
+        while i < bytes.len() {
+            assert!(bytes[i].is_ascii_digit(), "expecting a four digit year");
+            year = year * 10 + (bytes[i] - b'0') as u16;
+            i += 1;
+        }
+
+        assert!(year >= FIRST_YEAR, "expecting a year of 2015 or later");
+        Self(year)
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}", self.0)
+    }
+}
+
+impl PartialEq<u16> for Year {
+    fn eq(&self, other: &u16) -> bool {
+        self.0.eq(other)
+    }
+}
+
+impl PartialOrd<u16> for Year {
+    fn partial_cmp(&self, other: &u16) -> Option<std::cmp::Ordering> {
+        self.0.partial_cmp(other)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a year of 2015 or later")
+    }
+}
+
+/// An error which can be returned when reading the default [`Year`] from the environment.
+#[derive(Debug)]
+pub enum YearFromEnvError {
+    Missing,
+    Invalid(String),
+}
+
+impl Error for YearFromEnvError {}
+
+impl Display for YearFromEnvError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            YearFromEnvError::Missing => write!(
+                f,
+                "no year specified. Pass `--year <year>` or set the {YEAR_ENV_VAR} environment variable."
+            ),
+            YearFromEnvError::Invalid(value) => {
+                write!(f, "invalid {YEAR_ENV_VAR} value \"{value}\": {YearFromStrError}")
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Creates a [`Year`] value in a const context.
+///
+/// Without arguments, the year is read from the `AOC_YEAR` environment variable at compile time.
+#[macro_export]
+macro_rules! year {
+    () => {
+        $crate::Year::__parse_const(env!("AOC_YEAR"))
+    };
+    ($year:expr) => {{
+        const _ASSERT: () = assert!(
+            $year >= 2015 && $year <= 9999,
+            concat!(
+                "invalid year `",
+                $year,
+                "`, expecting a value of 2015 or later"
+            ),
+        );
+        $crate::Year::__new_unchecked($year)
+    }};
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Year;
+
+    #[test]
+    fn parses_valid_years() {
+        assert_eq!("2023".parse::<Year>().unwrap(), 2023);
+        assert_eq!("2015".parse::<Year>().unwrap(), 2015);
+    }
+
+    #[test]
+    fn rejects_invalid_years() {
+        assert!("2014".parse::<Year>().is_err());
+        assert!("23".parse::<Year>().is_err());
+        assert!("abcd".parse::<Year>().is_err());
+    }
+
+    #[test]
+    fn parses_in_const_context() {
+        const YEAR: Year = Year::__parse_const("2022");
+        assert_eq!(YEAR, 2022);
+    }
+}
+
+/* -------------------------------------------------------------------------- */
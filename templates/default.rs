@@ -0,0 +1,25 @@
+// {{title}}
+advent_of_code::solution!({{year}}, {{day}});
+
+pub fn part_one(input: &str) -> Option<{{answer}}> {
+    None
+}
+
+pub fn part_two(input: &str) -> Option<{{answer}}> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        advent_of_code::template::examples::check(PUZZLE, 1, part_one);
+    }
+
+    #[test]
+    fn test_part_two() {
+        advent_of_code::template::examples::check(PUZZLE, 2, part_two);
+    }
+}
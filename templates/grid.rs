@@ -0,0 +1,67 @@
+// {{title}}
+advent_of_code::solution!({{year}}, {{day}}, parse = parse_input);
+
+pub struct Grid {
+    cells: Vec<Vec<u8>>,
+    width: usize,
+    height: usize,
+}
+
+// helpers for the parts, remove the ones you do not need.
+#[allow(dead_code)]
+impl Grid {
+    fn get(&self, (x, y): (usize, usize)) -> Option<u8> {
+        self.cells.get(y)?.get(x).copied()
+    }
+
+    fn positions(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (0..self.height).flat_map(move |y| (0..self.width).map(move |x| (x, y)))
+    }
+
+    /// The neighbours of a cell within the grid, up, right, down and left.
+    fn neighbours(&self, (x, y): (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        [(0, -1), (1, 0), (0, 1), (-1, 0)]
+            .into_iter()
+            .filter_map(move |(dx, dy)| {
+                let position = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+                (position.0 < self.width && position.1 < self.height).then_some(position)
+            })
+    }
+}
+
+pub fn parse_input(input: &str) -> Grid {
+    let cells: Vec<Vec<u8>> = input.lines().map(|line| line.bytes().collect()).collect();
+
+    Grid {
+        width: cells.first().map_or(0, Vec::len),
+        height: cells.len(),
+        cells,
+    }
+}
+
+pub fn part_one(grid: &Grid) -> Option<{{answer}}> {
+    None
+}
+
+pub fn part_two(grid: &Grid) -> Option<{{answer}}> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        advent_of_code::template::examples::check(PUZZLE, 1, |input| {
+            part_one(&parse_input(input))
+        });
+    }
+
+    #[test]
+    fn test_part_two() {
+        advent_of_code::template::examples::check(PUZZLE, 2, |input| {
+            part_two(&parse_input(input))
+        });
+    }
+}
@@ -0,0 +1,40 @@
+// {{title}}
+advent_of_code::solution!({{year}}, {{day}}, parse = parse_input);
+
+/// The numbers of every line of the input.
+pub fn parse_input(input: &str) -> Vec<Vec<i64>> {
+    input.lines().map(parse_line).collect()
+}
+
+fn parse_line(line: &str) -> Vec<i64> {
+    line.split(|c: char| !c.is_ascii_digit() && c != '-')
+        .filter_map(|number| number.parse().ok())
+        .collect()
+}
+
+pub fn part_one(lines: &[Vec<i64>]) -> Option<{{answer}}> {
+    None
+}
+
+pub fn part_two(lines: &[Vec<i64>]) -> Option<{{answer}}> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        advent_of_code::template::examples::check(PUZZLE, 1, |input| {
+            part_one(&parse_input(input))
+        });
+    }
+
+    #[test]
+    fn test_part_two() {
+        advent_of_code::template::examples::check(PUZZLE, 2, |input| {
+            part_two(&parse_input(input))
+        });
+    }
+}
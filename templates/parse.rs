@@ -0,0 +1,54 @@
+// {{title}}
+advent_of_code::solution!({{year}}, {{day}}, parse = parse_input);
+
+use regex::Regex;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    name: String,
+    values: Vec<{{answer}}>,
+}
+
+/// Parses lines like `name: 1, 2, 3`.
+pub fn parse_input(input: &str) -> Vec<Entry> {
+    let re = Regex::new(r"^(?<name>[^:]+):(?<values>.*)$").unwrap();
+
+    input
+        .lines()
+        .filter_map(|line| re.captures(line))
+        .map(|captures| Entry {
+            name: captures["name"].trim().to_string(),
+            values: captures["values"]
+                .split(|c: char| !c.is_ascii_digit() && c != '-')
+                .filter_map(|value| value.parse().ok())
+                .collect(),
+        })
+        .collect()
+}
+
+pub fn part_one(entries: &[Entry]) -> Option<{{answer}}> {
+    None
+}
+
+pub fn part_two(entries: &[Entry]) -> Option<{{answer}}> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        advent_of_code::template::examples::check(PUZZLE, 1, |input| {
+            part_one(&parse_input(input))
+        });
+    }
+
+    #[test]
+    fn test_part_two() {
+        advent_of_code::template::examples::check(PUZZLE, 2, |input| {
+            part_two(&parse_input(input))
+        });
+    }
+}